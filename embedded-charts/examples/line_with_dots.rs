@@ -3,7 +3,6 @@ use embedded_charts::{
     line::LineChart,
 };
 use embedded_graphics::{
-    geometry::OriginDimensions,
     pixelcolor::Rgb565,
     prelude::{Point, RgbColor, Size},
     Drawable,
@@ -53,8 +52,6 @@ fn main() {
     }
     println!("{:?}", line_plot.get_points());
 
-    line_plot.scale_points_to_display(&display);
-    // println!("{:?}", line_plot.get_points());
     line_plot.draw(&mut display).unwrap();
     let output_settings = OutputSettingsBuilder::new().scale(3).build();
     Window::new("Line with dots", &output_settings).show_static(&display);
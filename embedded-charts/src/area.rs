@@ -0,0 +1,236 @@
+use embedded_graphics::{
+    prelude::{Dimensions, DrawTarget, PixelColor, Point, Size},
+    primitives::Rectangle,
+    Drawable,
+};
+
+use crate::axis::Axis;
+use crate::Viewport;
+
+/// Fill of the region between a line and a baseline
+#[derive(Debug, Clone, Copy, bon::Builder)]
+pub struct Area<C> {
+    color: C,
+    /// Value the area is filled to, in chart coordinates. Clamped to the y axis range.
+    #[builder(default)]
+    baseline: i32,
+    /// Draw the line on top of the fill
+    #[builder(default = true)]
+    outline: bool,
+}
+
+impl<C: Copy> Area<C> {
+    pub fn color(&self) -> C {
+        self.color
+    }
+
+    pub fn baseline(&self) -> i32 {
+        self.baseline
+    }
+
+    pub fn outline(&self) -> bool {
+        self.outline
+    }
+}
+
+/// Chart of `SERIES` filled areas stacked on top of each other, series 0 at the bottom.
+///
+/// Each sample holds an x value and one value per series. Like bars with
+/// [`BarLayout::Stacked`](crate::bar::BarLayout::Stacked), positive values stack upward and
+/// negative values downward from zero.
+#[derive(Debug, bon::Builder)]
+pub struct StackedAreaChart<'a, C, const SAMPLES: usize, const SERIES: usize>
+where
+    C: Default,
+{
+    #[builder(skip = [None; SAMPLES])]
+    samples: [Option<(i32, [i32; SERIES])>; SAMPLES],
+    #[builder(default = Axis::default_x_axis())]
+    x_axis: Axis<'a, C>,
+    #[builder(default = Axis::default_y_axis())]
+    y_axis: Axis<'a, C>,
+    /// Color of each series
+    #[builder(default = core::array::from_fn(|_| C::default()))]
+    colors: [C; SERIES],
+}
+
+impl<C, const SAMPLES: usize, const SERIES: usize> StackedAreaChart<'_, C, SAMPLES, SERIES>
+where
+    C: Default,
+{
+    /// Adds a sample at `x`, dropping the oldest one when the chart is full
+    pub fn push(&mut self, x: i32, values: [i32; SERIES]) {
+        self.samples.rotate_right(1);
+        self.samples[0] = Some((x, values));
+    }
+
+    /// Samples, newest first
+    pub fn get_samples(&self) -> &[Option<(i32, [i32; SERIES])>; SAMPLES] {
+        &self.samples
+    }
+
+    /// Sets the y axis range to fit the stacked totals. Zero is always included as the series
+    /// are stacked from it.
+    pub fn auto_range(&mut self) {
+        let (mut min, mut max) = (0, 0);
+        for (_, values) in self.samples.iter().flatten() {
+            for (start, end) in stack(values) {
+                min = min.min(start.min(end));
+                max = max.max(start.max(end));
+            }
+        }
+        self.y_axis.fit_range(min, max);
+    }
+
+    /// Viewport the stacked series are scaled through onto `display`
+    pub fn viewport<D: Dimensions>(&self, display: &D) -> Viewport {
+        Viewport::from_axes(&self.x_axis, &self.y_axis, display)
+    }
+}
+
+/// Start and end of each stacked series, positive and negative values keep separate totals
+fn stack<const SERIES: usize>(values: &[i32; SERIES]) -> [(i32, i32); SERIES] {
+    let (mut positive, mut negative) = (0, 0);
+    values.map(|value| {
+        let total = match value < 0 {
+            true => &mut negative,
+            false => &mut positive,
+        };
+        let start = *total;
+        *total += value;
+        (start, *total)
+    })
+}
+
+impl<C, const SAMPLES: usize, const SERIES: usize> Drawable
+    for StackedAreaChart<'_, C, SAMPLES, SERIES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let viewport = self.viewport(target);
+        let scale = |x: i32, value: i32| viewport.scale_point_clamped(Point::new(x, value));
+        for pair in self.samples.windows(2) {
+            let (Some((x0, values0)), Some((x1, values1))) = (pair[0], pair[1]) else {
+                continue;
+            };
+            let (stack0, stack1) = (stack(&values0), stack(&values1));
+            // Bottom series first, each one owns the boundary to the series it is stacked on
+            for (((start0, end0), (start1, end1)), color) in
+                stack0.into_iter().zip(stack1).zip(&self.colors)
+            {
+                if (start0, start1) == (end0, end1) {
+                    continue;
+                }
+                fill_between(
+                    target,
+                    (scale(x0, end0), scale(x1, end1)),
+                    (scale(x0, start0), scale(x1, start1)),
+                    *color,
+                )?;
+            }
+        }
+
+        // Axes on top, so the fill does not cover them
+        self.x_axis.draw(target)?;
+        self.y_axis.draw(target)?;
+        Ok(())
+    }
+}
+
+/// y of the line from `start` to `end` at `x`
+fn y_at(start: Point, end: Point, x: i32) -> i32 {
+    if start.x == end.x {
+        return start.y;
+    }
+    start.y + (end.y - start.y) * (x - start.x) / (end.x - start.x)
+}
+
+/// Fills the region between line `first` and line `second` one pixel column at a time, so no
+/// framebuffer is needed and the lines may cross each other.
+///
+/// Both lines are given as start and end point and should span the same x range. A vertical
+/// `first` line fills the column up to both of its ends.
+pub(crate) fn fill_between<D>(
+    target: &mut D,
+    first: (Point, Point),
+    second: (Point, Point),
+    color: D::Color,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+    D::Color: PixelColor,
+{
+    let (start, end) = first;
+    for x in start.x.min(end.x)..=start.x.max(end.x) {
+        let (mut low, mut high) = if start.x == end.x {
+            (start.y.min(end.y), start.y.max(end.y))
+        } else {
+            let y = y_at(start, end, x);
+            (y, y)
+        };
+        let other = y_at(second.0, second.1, x);
+        low = low.min(other);
+        high = high.max(other);
+        let column = Rectangle::new(Point::new(x, low), Size::new(1, (high - low) as u32 + 1));
+        target.fill_solid(&column, color)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Rgb888},
+        prelude::RgbColor,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_fill_between_crossing_lines() {
+        let mut display = MockDisplay::new();
+        fill_between(
+            &mut display,
+            (Point::new(0, 0), Point::new(4, 4)),
+            (Point::new(0, 2), Point::new(4, 2)),
+            BinaryColor::On,
+        )
+        .unwrap();
+        display.assert_pattern(&[
+            "#    ", //
+            "##   ", //
+            "#####", //
+            "   ##", //
+            "    #",
+        ]);
+    }
+
+    #[test]
+    fn test_negative_values_stack_downward() {
+        let mut chart = StackedAreaChart::<Rgb888, 2, 3>::builder()
+            .x_axis(Axis::builder().max(10).build())
+            .colors([Rgb888::RED, Rgb888::GREEN, Rgb888::BLUE])
+            .build();
+        chart.push(0, [30, -20, 10]);
+        chart.push(10, [30, -20, 10]);
+        chart.auto_range();
+        assert_eq!((-20, 40), (chart.y_axis.min, chart.y_axis.max));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+        let viewport = chart.viewport(&display);
+        let color = |y: i32| display.get_pixel(viewport.scale_point(Point::new(5, y)));
+        assert_eq!(Some(Rgb888::RED), color(15));
+        assert_eq!(Some(Rgb888::GREEN), color(-10));
+        assert_eq!(Some(Rgb888::BLUE), color(35));
+    }
+}
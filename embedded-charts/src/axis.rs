@@ -1,15 +1,27 @@
 use core::default::Default;
 
-use axis_builder::{SetLegend, State};
 use embedded_graphics::{
     geometry::AnchorPoint,
-    prelude::{Dimensions, DrawTarget, PixelColor, Point, Primitive},
-    primitives::{Line, PrimitiveStyle, Rectangle, Triangle},
+    prelude::{Dimensions, DrawTarget, PixelColor, Point, Primitive, Size},
+    primitives::{Line, PrimitiveStyle, Triangle},
     Drawable,
 };
-use u8g2_fonts::types::{FontColor, HorizontalAlignment, VerticalPosition};
+use u8g2_fonts::types::{HorizontalAlignment, VerticalPosition};
+
+use crate::style::{LineStyle, Stroke};
+use crate::Viewport;
+
+/// Space left between the end of the axis line and the display edge, enough for the arrow
+const END_PADDING: i32 = 8;
+/// Length of the arrow drawn at the end of the axis line
+const ARROW_LENGTH: i32 = 5;
+/// How far the arrow reaches sideways from the axis line
+const ARROW_HALF_WIDTH: i32 = 3;
 
 /// Default origin is in 0,0 which probably does not work for you.
+///
+/// `starting_point_offset` is measured from the bottom left corner of the display
+/// with y growing upward, like the data drawn on the axis.
 #[derive(Debug, bon::Builder)]
 pub struct Axis<'a, C>
 where
@@ -28,6 +40,8 @@ where
     color: C,
     #[builder(default = 2)]
     line_width: u32,
+    #[builder(default)]
+    line_style: LineStyle<'a>,
     #[builder(default = [3,3], into, setters(vis = ""))]
     starting_point_offset: Point,
     #[builder(default)]
@@ -41,13 +55,13 @@ impl<C> Axis<'_, C>
 where
     C: Default,
 {
+    /// Display coordinates where the axis line starts
     pub fn calculate_starting_coordinates<D: Dimensions>(&self, display: &D) -> Point {
         let bounding_box = display.bounding_box();
-        let height = bounding_box.size.height;
-        let mut start_point = self.starting_point_offset;
-        start_point.y = height as i32 - start_point.y;
+        let mut start_point = self.offset_to_display(&bounding_box.size);
 
         // TODO merge horizontal and vertical if possible as much as possible
+        // A legend the font can not render takes no space, `text::draw` skips it
         match self.direction {
             Direction::Horizontal => {
                 if self.show_legend {
@@ -57,14 +71,10 @@ where
                         VerticalPosition::Bottom,
                         HorizontalAlignment::Center,
                     );
-                    match legend_result {
-                        Ok(Some(r)) => {
-                            start_point.y -= r.top_left.y.abs_diff(start_point.y) as i32
-                                + self.line_width as i32
-                                + 1;
-                        }
-                        Ok(_) => (),
-                        Err(_) => todo!(),
+                    if let Ok(Some(r)) = legend_result {
+                        start_point.y -= r.top_left.y.abs_diff(start_point.y) as i32
+                            + self.line_width as i32
+                            + 1;
                     }
                 }
             }
@@ -76,12 +86,8 @@ where
                         VerticalPosition::Center,
                         HorizontalAlignment::Left,
                     );
-                    match legend_result {
-                        Ok(Some(r)) => {
-                            start_point.x += r.size.width as i32;
-                        }
-                        Ok(_) => (),
-                        Err(_) => todo!(),
+                    if let Ok(Some(r)) = legend_result {
+                        start_point.x += r.size.width as i32;
                     }
                 }
             }
@@ -89,6 +95,53 @@ where
         start_point
     }
 
+    /// Display coordinates where the axis line ends and the arrow starts
+    pub fn calculate_end_coordinates<D: Dimensions>(&self, display: &D) -> Point {
+        let size = display.bounding_box().size;
+        let start_point = self.calculate_starting_coordinates(display);
+        match self.direction {
+            Direction::Horizontal => Point::new(size.width as i32 - END_PADDING, start_point.y),
+            Direction::Vertical => Point::new(start_point.x, END_PADDING),
+        }
+    }
+
+    /// Display coordinates of the arrow tip, the maximum value of the axis is drawn here
+    pub fn calculate_tip_coordinates<D: Dimensions>(&self, display: &D) -> Point {
+        let end_point = self.calculate_end_coordinates(display);
+        match self.direction {
+            Direction::Horizontal => end_point + Point::new(ARROW_LENGTH, 0),
+            Direction::Vertical => end_point - Point::new(0, ARROW_LENGTH),
+        }
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub(crate) fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Sets the range to fit values from `min` to `max`. An empty range is widened by one so
+    /// values can still be scaled onto the axis.
+    pub fn fit_range(&mut self, min: i32, max: i32) {
+        self.min = min;
+        self.max = max.max(min + 1);
+    }
+
+    /// Distance from the axis line to the closest pixel not covered by the axis line or arrow
+    pub fn clearance(&self) -> i32 {
+        (self.line_width as i32 / 2).max(ARROW_HALF_WIDTH) + 1
+    }
+
+    /// Converts `starting_point_offset` from y up to display coordinates
+    fn offset_to_display(&self, display_size: &Size) -> Point {
+        Point::new(
+            self.starting_point_offset.x,
+            display_size.height as i32 - self.starting_point_offset.y,
+        )
+    }
+
     pub fn update_starting_offset(&mut self, new_offset: Point) {
         self.starting_point_offset = new_offset;
     }
@@ -106,6 +159,46 @@ impl<C> Axis<'_, C>
 where
     C: PixelColor + Default,
 {
+    /// Draws a small arrow outside the axis pointing at `value`, e.g. to mark a trigger level.
+    /// Values outside the axis range point at its closest end.
+    ///
+    /// The arrow takes `ARROW_LENGTH` pixels plus half the line width outside the axis. With
+    /// less room, e.g. at the default offset of 3 pixels, it is moved onto the axis line to
+    /// stay on the display.
+    pub fn draw_value_marker<D>(&self, target: &mut D, value: i32, color: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let value = value.clamp(self.min, self.max);
+        let start_point = self.calculate_starting_coordinates(target);
+        let tip_point = self.calculate_tip_coordinates(target);
+        let gap = self.line_width as i32 / 2 + 1;
+        let bounding_box = target.bounding_box();
+        let (tip, back, side) = match self.direction {
+            Direction::Horizontal => {
+                let x = crate::scale_value(value, self.min, self.max, start_point.x, tip_point.x);
+                let bottom = bounding_box.top_left.y + bounding_box.size.height as i32 - 1;
+                (
+                    Point::new(x, (start_point.y + gap).min(bottom - ARROW_LENGTH)),
+                    Point::new(0, ARROW_LENGTH),
+                    Point::new(ARROW_HALF_WIDTH, 0),
+                )
+            }
+            Direction::Vertical => {
+                let y = crate::scale_value(value, self.min, self.max, start_point.y, tip_point.y);
+                let left = bounding_box.top_left.x;
+                (
+                    Point::new((start_point.x - gap).max(left + ARROW_LENGTH), y),
+                    Point::new(-ARROW_LENGTH, 0),
+                    Point::new(0, ARROW_HALF_WIDTH),
+                )
+            }
+        };
+        Triangle::new(tip, tip + back + side, tip + back - side)
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(target)
+    }
+
     // TODO combine x and y triangle to single function
     fn x_triangle(
         &self,
@@ -117,15 +210,14 @@ where
         };
         let triangle_point2 = Point {
             x: end_point.x,
-            y: end_point.y - 3,
+            y: end_point.y - ARROW_HALF_WIDTH,
         };
         let triangle_point3 = Point {
-            x: end_point.x + 5,
+            x: end_point.x + ARROW_LENGTH,
             y: end_point.y,
         };
-        let triangle = Triangle::new(triangle_point1, triangle_point2, triangle_point3)
-            .into_styled(PrimitiveStyle::with_fill(self.color));
-        triangle
+        Triangle::new(triangle_point1, triangle_point2, triangle_point3)
+            .into_styled(PrimitiveStyle::with_fill(self.color))
     }
 
     fn y_triangle(
@@ -137,16 +229,15 @@ where
             y: end_point.y,
         };
         let triangle_point2 = Point {
-            x: end_point.x - 3,
+            x: end_point.x - ARROW_HALF_WIDTH,
             y: end_point.y,
         };
         let triangle_point3 = Point {
             x: end_point.x,
-            y: end_point.y - 5,
+            y: end_point.y - ARROW_LENGTH,
         };
-        let triangle = Triangle::new(triangle_point1, triangle_point2, triangle_point3)
-            .into_styled(PrimitiveStyle::with_fill(self.color));
-        triangle
+        Triangle::new(triangle_point1, triangle_point2, triangle_point3)
+            .into_styled(PrimitiveStyle::with_fill(self.color))
     }
 }
 
@@ -165,84 +256,220 @@ where
     where
         D: DrawTarget<Color = Self::Color>,
     {
-        let end_padding = 8; // enough for triangle
-
         if !self.visible {
             todo!();
             // return Ok(());
         }
 
-        let width = target.bounding_box().size.width;
-        let height = target.bounding_box().size.height;
-        let mut start_point = self.starting_point_offset;
-        start_point.y = height as i32 - start_point.y;
+        if self.show_legend {
+            let (anchor, vertical_position, horizontal_alignment) = match self.direction {
+                Direction::Horizontal => (
+                    AnchorPoint::BottomCenter,
+                    VerticalPosition::Bottom,
+                    HorizontalAlignment::Center,
+                ),
+                Direction::Vertical => (
+                    AnchorPoint::CenterLeft,
+                    VerticalPosition::Center,
+                    HorizontalAlignment::Left,
+                ),
+            };
+            let position = target.bounding_box().anchor_point(anchor);
+            crate::text::draw(
+                target,
+                self.legend,
+                position,
+                vertical_position,
+                horizontal_alignment,
+                self.color,
+            )?;
+        }
+
+        let start_point = self.calculate_starting_coordinates(target);
+        let end_point = self.calculate_end_coordinates(target);
+        Stroke::new(self.line_style, self.color, self.line_width).draw_line(
+            target,
+            start_point,
+            end_point,
+        )?;
 
         match self.direction {
+            Direction::Horizontal => self.x_triangle(&end_point).draw(target)?,
+            Direction::Vertical => self.y_triangle(end_point).draw(target)?,
+        }
+        Ok(start_point)
+    }
+}
+
+/// Axis of `N` categories instead of a numeric range.
+///
+/// The axis line is split into equal bands, one per label, numbered from left to right or from
+/// top to bottom. Labels are drawn below a horizontal axis and left of a vertical one, centred
+/// on a tick in the middle of their band, and shortened when they do not fit. Move the axis with
+/// [`Axis::update_starting_offset`] to leave room for them.
+#[derive(Debug, bon::Builder)]
+pub struct CategoryAxis<'a, C, const N: usize>
+where
+    C: Default,
+{
+    /// Draws the line, its `min` and `max` are not used
+    #[builder(default = Axis::default_x_axis())]
+    axis: Axis<'a, C>,
+    #[builder(default = [""; N])]
+    labels: [&'a str; N],
+    #[builder(default = 2)]
+    tick_length: u32,
+}
+
+impl<'a, C, const N: usize> CategoryAxis<'a, C, N>
+where
+    C: Default,
+{
+    pub fn axis(&self) -> &Axis<'a, C> {
+        &self.axis
+    }
+
+    pub fn axis_mut(&mut self) -> &mut Axis<'a, C> {
+        &mut self.axis
+    }
+
+    pub fn labels(&self) -> &[&'a str; N] {
+        &self.labels
+    }
+
+    pub fn set_label(&mut self, index: usize, label: &'a str) {
+        self.labels[index] = label;
+    }
+
+    /// First and last display coordinate along the axis covered by band `index`. Neighbouring
+    /// bands share their boundary pixel.
+    pub fn band<D: Dimensions>(&self, display: &D, index: usize) -> (i32, i32) {
+        let start = self.axis.calculate_starting_coordinates(display);
+        let end = self.axis.calculate_end_coordinates(display);
+        // Bands start past the axis crossing at the origin
+        let (first, length) = match self.axis.direction {
+            Direction::Horizontal => (
+                start.x + self.axis.clearance(),
+                end.x - start.x - self.axis.clearance(),
+            ),
+            Direction::Vertical => (end.y, start.y - self.axis.clearance() - end.y),
+        };
+        (
+            first + length * index as i32 / N as i32,
+            first + length * (index as i32 + 1) / N as i32,
+        )
+    }
+
+    /// Display coordinate along the axis of the middle of band `index`
+    pub fn center<D: Dimensions>(&self, display: &D, index: usize) -> i32 {
+        let (first, last) = self.band(display, index);
+        (first + last) / 2
+    }
+
+    /// Direction of the value axis, perpendicular to this one. Bars and boxes grow in it.
+    pub fn value_direction(&self) -> Direction {
+        match self.axis.direction {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        }
+    }
+
+    /// Viewport of this axis and `value_axis` on `display`, with the value axis as the y axis
+    /// when it is vertical and as the x axis when it is horizontal
+    pub fn viewport<D: Dimensions>(&self, value_axis: &Axis<C>, display: &D) -> Viewport {
+        match self.value_direction() {
+            Direction::Vertical => Viewport::from_axes(&self.axis, value_axis, display),
+            Direction::Horizontal => Viewport::from_axes(value_axis, &self.axis, display),
+        }
+    }
+
+    /// Display coordinate of `value` along the value axis of `viewport`, see
+    /// [`CategoryAxis::viewport`]. Values are kept off this axis line.
+    pub fn value_position(&self, viewport: &Viewport, value: i32) -> i32 {
+        let clearance = self.axis.clearance();
+        match self.value_direction() {
+            Direction::Vertical => {
+                let p = viewport.scale_point_clamped(Point::new(viewport.x_min, value));
+                p.y.min(viewport.origin.y - clearance)
+            }
             Direction::Horizontal => {
-                if self.show_legend {
-                    let legend_result = crate::DEFAULT_FONT.render_aligned(
-                        self.legend,
-                        target
-                            .bounding_box()
-                            .anchor_point(AnchorPoint::BottomCenter),
-                        VerticalPosition::Bottom,
-                        HorizontalAlignment::Center,
-                        FontColor::Transparent(self.color),
-                        target,
-                    );
-                    match legend_result {
-                        Ok(Some(r)) => {
-                            // start_point.x += r.top_left.x;
-                            // TODO check this, probably not correct
-                            start_point.y -= r.top_left.y.abs_diff(start_point.y) as i32
-                                + self.line_width as i32
-                                + 1;
-                        }
-                        Ok(_) => todo!(),
-                        Err(_) => todo!(),
+                let p = viewport.scale_point_clamped(Point::new(value, viewport.y_min));
+                p.x.max(viewport.origin.x + clearance)
+            }
+        }
+    }
+}
+
+impl<C, const N: usize> Drawable for CategoryAxis<'_, C, N>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = Point;
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let start_point = self.axis.draw(target)?;
+        let color = self.axis.color;
+        // Ticks start at the outer edge of the axis line
+        let edge = self.axis.line_width.div_ceil(2) as i32;
+        let tick = self.tick_length as i32;
+        let tick_style = PrimitiveStyle::with_stroke(color, 1);
+        for (index, label) in self.labels.iter().enumerate() {
+            let center = self.center(target, index);
+            let (first, last) = self.band(target, index);
+            let (tick_start, tick_end, label_position, max_width, vertical, horizontal) =
+                match self.axis.direction {
+                    Direction::Horizontal => {
+                        let y = start_point.y + edge;
+                        (
+                            Point::new(center, y),
+                            Point::new(center, y + tick - 1),
+                            Point::new(center, y + tick + 1),
+                            last - first,
+                            VerticalPosition::Top,
+                            HorizontalAlignment::Center,
+                        )
                     }
-                }
-                let end_point = Point::new(width as i32 - end_padding, start_point.y);
-                Line::new(start_point, end_point)
-                    .into_styled(PrimitiveStyle::with_stroke(self.color, self.line_width))
+                    Direction::Vertical => {
+                        let x = start_point.x - edge;
+                        (
+                            Point::new(x, center),
+                            Point::new(x - tick + 1, center),
+                            Point::new(x - tick - 1, center),
+                            x - tick - 1 - target.bounding_box().top_left.x,
+                            VerticalPosition::Center,
+                            HorizontalAlignment::Right,
+                        )
+                    }
+                };
+            if tick > 0 {
+                Line::new(tick_start, tick_end)
+                    .into_styled(tick_style)
                     .draw(target)?;
-
-                let triangle = self.x_triangle(&end_point);
-                triangle.draw(target)?;
             }
-            Direction::Vertical => {
-                if self.show_legend {
-                    let legend_result = crate::DEFAULT_FONT.render_aligned(
-                        self.legend,
-                        target.bounding_box().anchor_point(AnchorPoint::CenterLeft),
-                        VerticalPosition::Center,
-                        HorizontalAlignment::Left,
-                        FontColor::Transparent(self.color),
-                        target,
-                    );
-                    match legend_result {
-                        Ok(Some(r)) => {
-                            start_point.x += r.size.width as i32;
-                        }
-                        Ok(_) => todo!(),
-                        Err(_) => todo!(),
-                    }
+            match crate::text::fit(label, max_width) {
+                (label, false) => {
+                    crate::text::draw(target, label, label_position, vertical, horizontal, color)?
                 }
-                let end_point = Point::new(start_point.x, end_padding);
-
-                Line::new(start_point, end_point)
-                    .into_styled(PrimitiveStyle::with_stroke(self.color, self.line_width))
-                    .draw(target)?;
-
-                let triangle = self.y_triangle(end_point);
-                triangle.draw(target)?;
+                ("", true) => (),
+                (label, true) => crate::text::draw(
+                    target,
+                    format_args!("{label}."),
+                    label_position,
+                    vertical,
+                    horizontal,
+                    color,
+                )?,
             }
         }
         Ok(start_point)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Horizontal,
     Vertical,
@@ -251,6 +478,7 @@ pub enum Direction {
 /// Helper type for having one vertical and one horizontal axis with same starting point
 pub struct AxisPair {}
 
+#[allow(clippy::result_unit_err)]
 pub fn calibrate_starting_points<Color, D>(
     horizontal: &mut Axis<Color>,
     vertical: &mut Axis<Color>,
@@ -282,3 +510,56 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::Size, primitives::Rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_value_marker_outside_range_points_at_end() {
+        let axis = Axis::builder()
+            .direction(Direction::Vertical)
+            .max(36)
+            .build();
+        // Drawing outside the mock display panics, the marker has to stay on it
+        let mut clamped = MockDisplay::new();
+        axis.draw_value_marker(&mut clamped, 50, BinaryColor::On)
+            .unwrap();
+        let mut at_end = MockDisplay::new();
+        axis.draw_value_marker(&mut at_end, 36, BinaryColor::On)
+            .unwrap();
+        assert!(!at_end.affected_area().is_zero_sized());
+        clamped.assert_eq(&at_end);
+    }
+
+    #[test]
+    fn test_category_bands_cover_axis() {
+        let display = Rectangle::new(Point::zero(), Size::new(64, 64));
+        let axis = CategoryAxis::<(), 4>::builder().build();
+        let start = axis.axis().calculate_starting_coordinates(&display);
+        let end = axis.axis().calculate_end_coordinates(&display);
+        assert_eq!(start.x + axis.axis().clearance(), axis.band(&display, 0).0);
+        assert_eq!(end.x, axis.band(&display, 3).1);
+        for i in 0..3 {
+            assert_eq!(axis.band(&display, i).1, axis.band(&display, i + 1).0);
+        }
+    }
+
+    #[test]
+    fn test_values_kept_off_category_axis_line() {
+        let display = Rectangle::new(Point::zero(), Size::new(64, 64));
+        let axis = CategoryAxis::<BinaryColor, 4>::builder().build();
+        let mut value_axis = Axis::default_y_axis();
+        value_axis.fit_range(5, 5);
+        assert_eq!((5, 6), (value_axis.min, value_axis.max));
+        let viewport = axis.viewport(&value_axis, &display);
+        let line = viewport.origin.y - axis.axis().clearance();
+        assert_eq!(line, axis.value_position(&viewport, 5));
+        assert_eq!(line, axis.value_position(&viewport, -10));
+        assert!(axis.value_position(&viewport, 6) < line);
+    }
+}
@@ -0,0 +1,324 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::AnchorPoint,
+    prelude::{Dimensions, PixelColor, Point},
+    primitives::{Primitive, PrimitiveStyle, Rectangle},
+    Drawable,
+};
+use u8g2_fonts::types::{HorizontalAlignment, VerticalPosition};
+
+use crate::axis::{Axis, CategoryAxis, Direction};
+use crate::label::{LabelPosition, ValueLabel};
+use crate::Viewport;
+
+/// How the values of several series are placed in the slot of a bar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BarLayout {
+    /// Every series starts from zero, later series are drawn over the earlier ones
+    #[default]
+    Overlap,
+    /// Series are stacked on top of each other in order. Positive values stack upward and
+    /// negative values downward from zero.
+    Stacked,
+    /// Series are placed side by side in the slot of a bar, each starting from zero
+    Grouped,
+}
+
+/// Bar chart with `BARS` bars, each showing `SERIES` values.
+///
+/// Each bar takes one band of the category axis. Bars grow away from the category axis, so a
+/// vertical category axis gives horizontal bars listed from the top down. The value axis is
+/// always turned perpendicular to the category axis.
+#[derive(Debug, bon::Builder)]
+#[builder(finish_fn(name = build_unaligned, vis = ""))]
+pub struct BarChart<'a, C, const BARS: usize, const SERIES: usize = 1>
+where
+    C: Default,
+{
+    #[builder(default = [[0; SERIES]; BARS])]
+    values: [[i32; SERIES]; BARS],
+    #[builder(default = CategoryAxis::builder().build())]
+    category_axis: CategoryAxis<'a, C, BARS>,
+    #[builder(default = Axis::default_y_axis())]
+    value_axis: Axis<'a, C>,
+    /// Color of each series
+    #[builder(default = core::array::from_fn(|_| C::default()))]
+    colors: [C; SERIES],
+    #[builder(default)]
+    layout: BarLayout,
+    /// Pixels left empty between neighbouring bars or groups of bars
+    #[builder(default = 2)]
+    outer_padding: u32,
+    /// Pixels left empty between the bars of a group with [`BarLayout::Grouped`]
+    #[builder(default = 1)]
+    inner_padding: u32,
+    /// Writes the value of each bar at its end
+    value_labels: Option<ValueLabel<'a, C>>,
+}
+
+impl<C, const BARS: usize, const SERIES: usize> BarChart<'_, C, BARS, SERIES>
+where
+    C: Default,
+{
+    pub fn get_values(&self) -> &[[i32; SERIES]; BARS] {
+        &self.values
+    }
+
+    pub fn set_values(&mut self, values: [[i32; SERIES]; BARS]) {
+        self.values = values;
+    }
+
+    pub fn set(&mut self, bar: usize, series: usize, value: i32) {
+        self.values[bar][series] = value;
+    }
+
+    /// Sets the value axis range to fit the values, including the stacked totals with
+    /// [`BarLayout::Stacked`]. Zero is always included as the bars start from it.
+    pub fn auto_range(&mut self) {
+        let (mut min, mut max) = (0, 0);
+        for values in &self.values {
+            for (low, high) in self.extents(values) {
+                min = min.min(low);
+                max = max.max(high);
+            }
+        }
+        self.value_axis.fit_range(min, max);
+    }
+
+    /// Direction the bars grow in
+    pub fn orientation(&self) -> Direction {
+        self.category_axis.value_direction()
+    }
+
+    /// Value range covered by each series of a bar
+    fn extents(&self, values: &[i32; SERIES]) -> [(i32, i32); SERIES] {
+        let (mut positive, mut negative) = (0, 0);
+        values.map(|value| {
+            let start = match (self.layout, value < 0) {
+                (BarLayout::Overlap | BarLayout::Grouped, _) => 0,
+                (BarLayout::Stacked, false) => positive,
+                (BarLayout::Stacked, true) => negative,
+            };
+            let end = start + value;
+            if self.layout == BarLayout::Stacked {
+                match value < 0 {
+                    true => negative = end,
+                    false => positive = end,
+                }
+            }
+            (start.min(end), start.max(end))
+        })
+    }
+
+    /// See [`CategoryAxis::viewport`]
+    pub fn viewport<D: Dimensions>(&self, display: &D) -> Viewport {
+        self.category_axis.viewport(&self.value_axis, display)
+    }
+
+    /// Span of `series` along the category axis in the slot from `start` to `end`
+    fn series_band(&self, start: i32, end: i32, series: usize) -> (i32, i32) {
+        if self.layout != BarLayout::Grouped {
+            return (start, end);
+        }
+        // Every bar gets an equal share of the slot, the padding is taken from the right
+        let inner = self.inner_padding as i32;
+        let span = end - start + 1 + inner;
+        (
+            start + span * series as i32 / SERIES as i32,
+            start + span * (series as i32 + 1) / SERIES as i32 - inner - 1,
+        )
+    }
+}
+
+impl<'a, C, const BARS: usize, const SERIES: usize, S> BarChartBuilder<'a, C, BARS, SERIES, S>
+where
+    C: Default,
+    S: bar_chart_builder::IsComplete,
+{
+    /// Builds the chart, turning the value axis perpendicular to the category axis
+    pub fn build(self) -> BarChart<'a, C, BARS, SERIES> {
+        let mut chart = self.build_unaligned();
+        let orientation = chart.orientation();
+        chart.value_axis.set_direction(orientation);
+        chart
+    }
+}
+
+impl<C, const BARS: usize, const SERIES: usize> Drawable for BarChart<'_, C, BARS, SERIES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.category_axis.draw(target)?;
+        self.value_axis.draw(target)?;
+
+        for bar in 0..BARS {
+            for (series, color) in self.colors.iter().enumerate() {
+                if let Some(rectangle) = self.bar_rectangle(target, bar, series) {
+                    rectangle
+                        .into_styled(PrimitiveStyle::with_fill(*color))
+                        .draw(target)?;
+                }
+            }
+        }
+        // Labels go on top, so later bars of a stack can not cover them
+        if let Some(label) = &self.value_labels {
+            for bar in 0..BARS {
+                for series in 0..SERIES {
+                    self.draw_value_label(target, label, bar, series)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C, const BARS: usize, const SERIES: usize> BarChart<'_, C, BARS, SERIES>
+where
+    C: PixelColor + Default,
+{
+    /// Area covered by `series` of `bar`, `None` if the bar is empty
+    fn bar_rectangle<D: Dimensions>(
+        &self,
+        display: &D,
+        bar: usize,
+        series: usize,
+    ) -> Option<Rectangle> {
+        let viewport = self.viewport(display);
+        let position = |value: i32| self.category_axis.value_position(&viewport, value);
+        let half_padding = self.outer_padding as i32 / 2;
+        let (start, end) = self.category_axis.band(display, bar);
+        let (start, end) = self.series_band(start + half_padding, end - half_padding, series);
+        let (low, high) = self.extents(&self.values[bar])[series];
+        if low == high || start > end {
+            return None;
+        }
+        let (low, high) = (position(low), position(high));
+        Some(match self.orientation() {
+            Direction::Vertical => {
+                Rectangle::with_corners(Point::new(start, high), Point::new(end, low))
+            }
+            Direction::Horizontal => {
+                Rectangle::with_corners(Point::new(low, start), Point::new(high, end))
+            }
+        })
+    }
+
+    fn draw_value_label<D>(
+        &self,
+        target: &mut D,
+        label: &ValueLabel<C>,
+        bar: usize,
+        series: usize,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let Some(rectangle) = self.bar_rectangle(target, bar, series) else {
+            return Ok(());
+        };
+        let value = self.values[bar][series];
+        // Middle of the end of the bar and the direction the bar grows in
+        let (end, outward) = match (self.orientation(), value < 0) {
+            (Direction::Vertical, false) => (AnchorPoint::TopCenter, Point::new(0, -1)),
+            (Direction::Vertical, true) => (AnchorPoint::BottomCenter, Point::new(0, 1)),
+            (Direction::Horizontal, false) => (AnchorPoint::CenterRight, Point::new(1, 0)),
+            (Direction::Horizontal, true) => (AnchorPoint::CenterLeft, Point::new(-1, 0)),
+        };
+        let (position, towards) = match label.position() {
+            LabelPosition::Outside => (rectangle.anchor_point(end) + outward * 2, outward),
+            LabelPosition::Inside => (rectangle.anchor_point(end) - outward, outward * -1),
+        };
+        let (vertical, horizontal) = match (towards.x, towards.y) {
+            (0, -1) => (VerticalPosition::Bottom, HorizontalAlignment::Center),
+            (0, _) => (VerticalPosition::Top, HorizontalAlignment::Center),
+            (1, _) => (VerticalPosition::Center, HorizontalAlignment::Left),
+            _ => (VerticalPosition::Center, HorizontalAlignment::Right),
+        };
+        crate::text::draw(
+            target,
+            format_args!("{}", label.format(value)),
+            position,
+            vertical,
+            horizontal,
+            label.color(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use super::*;
+
+    #[test]
+    fn test_auto_range_fits_stacked_totals() {
+        let mut chart = BarChart::<BinaryColor, 2, 3>::builder()
+            .values([[3, 4, -2], [5, -1, -6]])
+            .layout(BarLayout::Stacked)
+            .build();
+        chart.auto_range();
+        assert_eq!((-7, 7), (chart.value_axis.min, chart.value_axis.max));
+        assert_eq!([(0, 5), (-1, 0), (-7, -1)], chart.extents(&[5, -1, -6]));
+    }
+
+    #[test]
+    fn test_grouped_bands_split_slot() {
+        let chart = BarChart::<BinaryColor, 1, 3>::builder()
+            .layout(BarLayout::Grouped)
+            .inner_padding(1)
+            .build();
+        let bands = [0, 1, 2].map(|series| chart.series_band(10, 20, series));
+        assert_eq!([(10, 12), (14, 16), (18, 20)], bands);
+    }
+
+    #[test]
+    fn test_horizontal_bars_listed_top_down() {
+        let mut chart = BarChart::<BinaryColor, 3>::builder()
+            .values([[10], [20], [30]])
+            .category_axis(CategoryAxis::builder().axis(Axis::default_y_axis()).build())
+            .colors([BinaryColor::On])
+            .build();
+        assert_eq!(Direction::Horizontal, chart.orientation());
+        assert_eq!(Direction::Horizontal, chart.value_axis.direction());
+        chart.auto_range();
+        assert_eq!((0, 30), (chart.value_axis.min, chart.value_axis.max));
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+        // Left and right end of the bar in each row, the axes are drawn in the off color
+        let row = |y: i32| {
+            let mut lit =
+                (0..64).filter(|&x| display.get_pixel(Point::new(x, y)) == Some(BinaryColor::On));
+            Some((lit.clone().next()?, lit.next_back()?))
+        };
+        let mut bars = [None; 3];
+        let mut count = 0;
+        let mut previous = None;
+        for y in 0..64 {
+            let current = row(y);
+            if current.is_some() && current != previous {
+                assert!(count < 3, "more than 3 bars");
+                bars[count] = current;
+                count += 1;
+            }
+            previous = current;
+        }
+        let bars = bars.map(Option::unwrap);
+        for pair in bars.windows(2) {
+            assert_eq!(pair[0].0, pair[1].0);
+            assert!(pair[0].1 < pair[1].1);
+        }
+        let viewport = chart.viewport(&display);
+        let end = viewport.scale_point(Point::new(30, viewport.y_min)).x;
+        assert_eq!(end, bars[2].1);
+    }
+}
@@ -0,0 +1,287 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    prelude::{Dimensions, PixelColor, Point},
+    primitives::{Circle, Line, Primitive, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle},
+    Drawable,
+};
+
+use crate::axis::{Axis, CategoryAxis, Direction};
+use crate::Viewport;
+
+/// Summary of a distribution drawn by [`BoxPlot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoxStats {
+    /// End of the lower whisker
+    pub low: i32,
+    pub q1: i32,
+    pub median: i32,
+    pub q3: i32,
+    /// End of the upper whisker
+    pub high: i32,
+    /// Smallest sample, an outlier when below `low`
+    pub min: i32,
+    /// Largest sample, an outlier when above `high`
+    pub max: i32,
+}
+
+impl BoxStats {
+    /// Statistics of `samples`, `None` if there are none.
+    ///
+    /// The quartiles are found by selection, which reorders `samples` in place instead of
+    /// sorting a copy. With `outliers` the whiskers end at the last samples within 1.5 times
+    /// the interquartile range from the box. Without it they reach the smallest and largest
+    /// sample. No samples are kept, give them to [`BoxPlot`] to draw every outlier.
+    pub fn from_samples(samples: &mut [i32], outliers: bool) -> Option<Self> {
+        let min = *samples.iter().min()?;
+        let max = *samples.iter().max()?;
+        let q1 = quantile(samples, 1, 4);
+        let median = quantile(samples, 1, 2);
+        let q3 = quantile(samples, 3, 4);
+        let (low, high) = if outliers {
+            let reach = (q3 as i64 - q1 as i64) * 3 / 2;
+            let (low_fence, high_fence) = (q1 as i64 - reach, q3 as i64 + reach);
+            let inside = || {
+                samples
+                    .iter()
+                    .filter(|s| (low_fence..=high_fence).contains(&(**s as i64)))
+            };
+            (inside().min().copied()?, inside().max().copied()?)
+        } else {
+            (min, max)
+        };
+        Some(Self {
+            low,
+            q1,
+            median,
+            q3,
+            high,
+            min,
+            max,
+        })
+    }
+}
+
+/// Value at `numerator / denominator` of the way through the sorted `samples`, interpolated
+/// between neighbouring samples
+fn quantile(samples: &mut [i32], numerator: usize, denominator: usize) -> i32 {
+    let position = (samples.len() - 1) * numerator;
+    let (index, fraction) = (position / denominator, position % denominator);
+    let (_, below, above) = samples.select_nth_unstable(index);
+    let below = *below as i64;
+    match above.iter().min() {
+        Some(next) if fraction > 0 => {
+            (below + (*next as i64 - below) * fraction as i64 / denominator as i64) as i32
+        }
+        _ => below as i32,
+    }
+}
+
+/// Box and whisker plot of `N` distributions, one per band of the category axis.
+///
+/// Boxes grow away from the category axis like the bars of a
+/// [`BarChart`](crate::bar::BarChart), so a vertical category axis gives horizontal boxes.
+#[derive(Debug, bon::Builder)]
+pub struct BoxPlot<'a, C, const N: usize>
+where
+    C: Default,
+{
+    #[builder(default = [None; N])]
+    stats: [Option<BoxStats>; N],
+    /// Samples of each distribution, every sample outside the whiskers is drawn as an
+    /// outlier. Without them only `min` and `max` of the stats are.
+    #[builder(default = [&[][..]; N])]
+    samples: [&'a [i32]; N],
+    #[builder(default = CategoryAxis::builder().build())]
+    category_axis: CategoryAxis<'a, C, N>,
+    #[builder(default = Axis::default_y_axis())]
+    value_axis: Axis<'a, C>,
+    /// Color of the box outline and whiskers
+    #[builder(default)]
+    color: C,
+    /// Fill of the box
+    fill_color: Option<C>,
+    #[builder(default)]
+    median_color: C,
+    /// Pixels left empty between neighbouring boxes
+    #[builder(default = 4)]
+    padding: u32,
+    /// Diameter of the outlier dots
+    #[builder(default = 3)]
+    outlier_diameter: u32,
+}
+
+impl<'a, C, const N: usize> BoxPlot<'a, C, N>
+where
+    C: Default,
+{
+    pub fn get_stats(&self) -> &[Option<BoxStats>; N] {
+        &self.stats
+    }
+
+    pub fn set(&mut self, index: usize, stats: Option<BoxStats>) {
+        self.stats[index] = stats;
+    }
+
+    /// Sets the samples the outliers of distribution `index` are drawn from
+    pub fn set_samples(&mut self, index: usize, samples: &'a [i32]) {
+        self.samples[index] = samples;
+    }
+
+    /// Sets the value axis range from the smallest to the largest sample
+    pub fn auto_range(&mut self) {
+        let stats = self.stats.iter().flatten();
+        let min = stats.clone().map(|s| s.min).min();
+        let max = stats.map(|s| s.max).max();
+        if let (Some(min), Some(max)) = (min, max) {
+            self.value_axis.fit_range(min, max);
+        }
+    }
+
+    /// Direction the boxes grow in
+    pub fn orientation(&self) -> Direction {
+        self.category_axis.value_direction()
+    }
+
+    /// See [`CategoryAxis::viewport`]
+    pub fn viewport<D: Dimensions>(&self, display: &D) -> Viewport {
+        self.category_axis.viewport(&self.value_axis, display)
+    }
+}
+
+impl<C, const N: usize> Drawable for BoxPlot<'_, C, N>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.category_axis.draw(target)?;
+        self.value_axis.draw(target)?;
+
+        let viewport = self.viewport(target);
+        let orientation = self.orientation();
+        // Display point at `across` on the category axis and `value` on the value axis
+        let at = |across: i32, value: i32| {
+            let along = self.category_axis.value_position(&viewport, value);
+            match orientation {
+                Direction::Vertical => Point::new(across, along),
+                Direction::Horizontal => Point::new(along, across),
+            }
+        };
+        let line_style = PrimitiveStyle::with_stroke(self.color, 1);
+        let mut box_style = PrimitiveStyleBuilder::new()
+            .stroke_color(self.color)
+            .stroke_width(1);
+        if let Some(fill_color) = self.fill_color {
+            box_style = box_style.fill_color(fill_color);
+        }
+        let box_style = box_style.build();
+
+        let half_padding = self.padding as i32 / 2;
+        for (index, stats) in self.stats.iter().enumerate() {
+            let Some(stats) = stats else {
+                continue;
+            };
+            let (start, end) = self.category_axis.band(target, index);
+            let (start, end) = (start + half_padding, end - half_padding);
+            if start > end {
+                continue;
+            }
+            let center = (start + end) / 2;
+            // Whisker caps are half as wide as the box
+            let (cap_start, cap_end) = ((start + center) / 2, (center + end) / 2);
+
+            for (from, to) in [(stats.low, stats.q1), (stats.q3, stats.high)] {
+                Line::new(at(center, from), at(center, to))
+                    .into_styled(line_style)
+                    .draw(target)?;
+            }
+            for value in [stats.low, stats.high] {
+                Line::new(at(cap_start, value), at(cap_end, value))
+                    .into_styled(line_style)
+                    .draw(target)?;
+            }
+            Rectangle::with_corners(at(start, stats.q1), at(end, stats.q3))
+                .into_styled(box_style)
+                .draw(target)?;
+            Line::new(at(start, stats.median), at(end, stats.median))
+                .into_styled(PrimitiveStyle::with_stroke(self.median_color, 1))
+                .draw(target)?;
+            // Without the samples only the most extreme outliers are known
+            let samples = self.samples[index];
+            let extremes = samples.is_empty().then_some([stats.min, stats.max]);
+            let outliers = samples
+                .iter()
+                .copied()
+                .chain(extremes.into_iter().flatten())
+                .filter(|value| !(stats.low..=stats.high).contains(value));
+            for outlier in outliers {
+                Circle::with_center(at(center, outlier), self.outlier_diameter)
+                    .into_styled(line_style)
+                    .draw(target)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use super::*;
+
+    #[test]
+    fn test_stats_of_samples() {
+        let mut samples = [7, 1, 9, 3, 5, 2, 8, 4, 6];
+        let stats = BoxStats::from_samples(&mut samples, false).unwrap();
+        assert_eq!(
+            BoxStats {
+                low: 1,
+                q1: 3,
+                median: 5,
+                q3: 7,
+                high: 9,
+                min: 1,
+                max: 9,
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn test_outliers_shorten_whiskers() {
+        let mut samples = [10, 11, 12, 13, 14, 15, 16, 17, 100];
+        let stats = BoxStats::from_samples(&mut samples, true).unwrap();
+        assert_eq!((10, 17, 100), (stats.low, stats.high, stats.max));
+        assert_eq!(None, BoxStats::from_samples(&mut [], true));
+    }
+
+    #[test]
+    fn test_every_outlier_drawn_with_samples() {
+        let mut samples = [10, 11, 12, 13, 14, 15, 16, 17, 40, 100];
+        let stats = BoxStats::from_samples(&mut samples, true).unwrap();
+        let lit_at = |samples: &[i32], value: i32| {
+            let mut plot = BoxPlot::<BinaryColor, 1>::builder()
+                .stats([Some(stats)])
+                .samples([samples])
+                .color(BinaryColor::On)
+                .build();
+            plot.auto_range();
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            plot.draw(&mut display).unwrap();
+            let viewport = plot.viewport(&display);
+            let y = viewport.scale_point(Point::new(viewport.x_min, value)).y;
+            (0..64).any(|x| display.get_pixel(Point::new(x, y)) == Some(BinaryColor::On))
+        };
+        assert!(lit_at(&samples, 40));
+        assert!(lit_at(&samples, 100));
+        assert!(!lit_at(&[], 40));
+        assert!(lit_at(&[], 100));
+    }
+}
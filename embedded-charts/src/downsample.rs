@@ -0,0 +1,105 @@
+use embedded_graphics::prelude::Point;
+
+/// Largest-Triangle-Three-Buckets downsampling of `data` into `out`.
+///
+/// Keeps the first and last point and from every bucket in between the point forming the
+/// largest triangle with the previously kept point and the average of the next bucket, so
+/// the shape of the line is preserved. Returns the number of points written to `out`, which
+/// is `data.len()` if all the points fit.
+pub fn lttb(data: &[Point], out: &mut [Point]) -> usize {
+    let mut written = 0;
+    lttb_by(
+        data.len(),
+        |i| data[i],
+        out.len(),
+        |p| {
+            out[written] = p;
+            written += 1;
+        },
+    );
+    written
+}
+
+/// [`lttb`] over `len` points returned by `get`, calling `put` for at most `threshold` kept
+/// points in order
+pub(crate) fn lttb_by(
+    len: usize,
+    get: impl Fn(usize) -> Point,
+    threshold: usize,
+    mut put: impl FnMut(Point),
+) {
+    if threshold >= len {
+        (0..len).for_each(|i| put(get(i)));
+        return;
+    }
+    if threshold < 3 {
+        // No room for buckets, keep the ends
+        [0, len - 1]
+            .into_iter()
+            .take(threshold)
+            .for_each(|i| put(get(i)));
+        return;
+    }
+
+    let buckets = threshold - 2;
+    // Bucket `i` covers points `bound(i)..bound(i + 1)`, first and last point are left out
+    let bound = |i: usize| 1 + i * (len - 2) / buckets;
+
+    let mut selected = get(0);
+    put(selected);
+    for bucket in 0..buckets {
+        let next = if bucket + 1 < buckets {
+            bound(bucket + 1)..bound(bucket + 2)
+        } else {
+            len - 1..len
+        };
+        let count = next.len() as i64;
+        let (sum_x, sum_y) = next
+            .map(&get)
+            .fold((0i64, 0i64), |(x, y), p| (x + p.x as i64, y + p.y as i64));
+        let (average_x, average_y) = (sum_x / count, sum_y / count);
+
+        let (a_x, a_y) = (selected.x as i64, selected.y as i64);
+        let mut max_area = -1;
+        for i in bound(bucket)..bound(bucket + 1) {
+            let p = get(i);
+            // Twice the triangle area, the factor does not matter for comparing
+            let area = ((a_x - average_x) * (p.y as i64 - a_y)
+                - (a_x - p.x as i64) * (average_y - a_y))
+                .abs();
+            if area > max_area {
+                max_area = area;
+                selected = p;
+            }
+        }
+        put(selected);
+    }
+    put(get(len - 1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lttb_keeps_ends_and_spike() {
+        let mut data = [Point::zero(); 20];
+        for (x, p) in data.iter_mut().enumerate() {
+            *p = Point::new(x as i32, 0);
+        }
+        data[7].y = 50;
+        let mut out = [Point::zero(); 5];
+        assert_eq!(5, lttb(&data, &mut out));
+        assert_eq!(data[0], out[0]);
+        assert_eq!(data[19], out[4]);
+        assert!(out.contains(&data[7]));
+    }
+
+    #[test]
+    fn test_lttb_copies_when_it_fits() {
+        let data = [Point::new(0, 1), Point::new(1, 3), Point::new(2, 2)];
+        let mut out = [Point::zero(); 4];
+        assert_eq!(3, lttb(&data, &mut out));
+        assert_eq!(data, out[..3]);
+    }
+}
@@ -0,0 +1,389 @@
+use embedded_graphics::{
+    geometry::Angle,
+    prelude::{DrawTarget, PixelColor, Point, Primitive},
+    primitives::{
+        Arc, Circle, Line, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, StrokeAlignment,
+    },
+    Drawable,
+};
+use u8g2_fonts::types::{HorizontalAlignment, VerticalPosition};
+
+use crate::axis::Direction;
+use crate::label::ValueLabel;
+use crate::trig::point_at;
+
+/// Range of values marked in a color, e.g. green, yellow and red for normal, warning and
+/// critical. A zone with `start == end` is not drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Zone<C> {
+    pub start: i32,
+    pub end: i32,
+    pub color: C,
+}
+
+impl<C> Zone<C> {
+    pub fn new(start: i32, end: i32, color: C) -> Self {
+        Self { start, end, color }
+    }
+}
+
+/// How a [`RadialGauge`] shows its value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Indicator {
+    /// Line from the center pointing at the value
+    #[default]
+    Needle,
+    /// Arc `width` pixels wide filled from the start of the scale up to the value
+    Arc { width: u32 },
+}
+
+/// Dial gauge showing a value between `min` and `max` on a circular scale.
+///
+/// The scale runs clockwise over `sweep` from `start_angle`, which is measured clockwise
+/// from 3 o'clock. The gauge is the largest circle fitting the display, with the zones on
+/// its outer edge, ticks and tick labels inside them and the indicator on top.
+#[derive(Debug, bon::Builder)]
+pub struct RadialGauge<'a, C, const ZONES: usize = 0>
+where
+    C: Default,
+{
+    #[builder(default)]
+    value: i32,
+    #[builder(default)]
+    min: i32,
+    #[builder(default = 100)]
+    max: i32,
+    /// Defaults to the lower left, so the default sweep leaves the bottom open
+    #[builder(default = Angle::from_degrees(135.0))]
+    start_angle: Angle,
+    #[builder(default = Angle::from_degrees(270.0))]
+    sweep: Angle,
+    #[builder(default = core::array::from_fn(|_| Zone::default()))]
+    zones: [Zone<C>; ZONES],
+    /// Width of the ring the zones are drawn in
+    #[builder(default = 4)]
+    zone_width: u32,
+    /// Color of the scale line and the ticks
+    #[builder(default)]
+    color: C,
+    /// Number of intervals between the ticks, 0 for no ticks
+    #[builder(default = 5)]
+    ticks: u32,
+    #[builder(default = 3)]
+    tick_length: u32,
+    /// Writes the value of every tick next to it
+    tick_labels: Option<ValueLabel<'a, C>>,
+    #[builder(default)]
+    indicator: Indicator,
+    #[builder(default)]
+    indicator_color: C,
+}
+
+impl<C, const ZONES: usize> RadialGauge<'_, C, ZONES>
+where
+    C: Default,
+{
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value;
+    }
+
+    pub fn zones_mut(&mut self) -> &mut [Zone<C>; ZONES] {
+        &mut self.zones
+    }
+
+    /// Angle of `value` on the scale, values outside `min..=max` are clamped
+    pub fn angle_of(&self, value: i32) -> Angle {
+        let range = (self.max as i64 - self.min as i64).max(1);
+        let offset = (value as i64 - self.min as i64).clamp(0, range);
+        self.start_angle
+            + Angle::from_degrees(self.sweep.to_degrees() * offset as f32 / range as f32)
+    }
+
+    /// Value of tick `tick` out of `ticks`
+    fn tick_value(&self, tick: u32) -> i32 {
+        let range = self.max as i64 - self.min as i64;
+        (self.min as i64 + range * tick as i64 / self.ticks.max(1) as i64) as i32
+    }
+}
+
+impl<C, const ZONES: usize> Drawable for RadialGauge<'_, C, ZONES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bounding_box = target.bounding_box();
+        let center = bounding_box.center();
+        let diameter = bounding_box
+            .size
+            .width
+            .min(bounding_box.size.height)
+            .saturating_sub(1);
+        let radius = diameter as i32 / 2;
+        let ring = |width: u32, color: C| {
+            PrimitiveStyleBuilder::new()
+                .stroke_color(color)
+                .stroke_width(width)
+                .stroke_alignment(StrokeAlignment::Inside)
+                .build()
+        };
+
+        for zone in &self.zones {
+            if zone.start == zone.end || self.zone_width == 0 {
+                continue;
+            }
+            let start = self.angle_of(zone.start.min(zone.end));
+            let end = self.angle_of(zone.start.max(zone.end));
+            Arc::with_center(center, diameter, start, end - start)
+                .into_styled(ring(self.zone_width, zone.color))
+                .draw(target)?;
+        }
+
+        // Scale line on the inner edge of the zones
+        let scale_radius = radius - self.zone_width as i32;
+        let scale_diameter = (scale_radius * 2).max(0) as u32;
+        Arc::with_center(center, scale_diameter, self.start_angle, self.sweep)
+            .into_styled(ring(1, self.color))
+            .draw(target)?;
+
+        if let Indicator::Arc { width } = self.indicator {
+            let sweep = self.angle_of(self.value) - self.start_angle;
+            Arc::with_center(center, scale_diameter, self.start_angle, sweep)
+                .into_styled(ring(width, self.indicator_color))
+                .draw(target)?;
+        }
+
+        let tick_style = PrimitiveStyle::with_stroke(self.color, 1);
+        let inner_radius = scale_radius - self.tick_length as i32;
+        for tick in (0..=self.ticks).filter(|_| self.ticks > 0) {
+            let angle = self.angle_of(self.tick_value(tick));
+            Line::new(
+                point_at(center, scale_radius, angle),
+                point_at(center, inner_radius, angle),
+            )
+            .into_styled(tick_style)
+            .draw(target)?;
+        }
+        if let Some(label) = &self.tick_labels {
+            let indicator_width = match self.indicator {
+                Indicator::Needle => 0,
+                Indicator::Arc { width } => width as i32,
+            };
+            // Far enough in for the center of a line of the 4x6 font
+            let label_radius = scale_radius - (self.tick_length as i32).max(indicator_width) - 5;
+            for tick in (0..=self.ticks).filter(|_| self.ticks > 0) {
+                let value = self.tick_value(tick);
+                crate::text::draw(
+                    target,
+                    format_args!("{}", label.format(value)),
+                    point_at(center, label_radius, self.angle_of(value)),
+                    VerticalPosition::Center,
+                    HorizontalAlignment::Center,
+                    label.color(),
+                )?;
+            }
+        }
+
+        if self.indicator == Indicator::Needle {
+            let tip = point_at(center, scale_radius - 1, self.angle_of(self.value));
+            Line::new(center, tip)
+                .into_styled(PrimitiveStyle::with_stroke(self.indicator_color, 1))
+                .draw(target)?;
+            Circle::with_center(center, 3)
+                .into_styled(PrimitiveStyle::with_fill(self.indicator_color))
+                .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bar gauge showing a value between `min` and `max`, like a progress bar or bullet chart.
+///
+/// The gauge fills the display inside a one pixel outline. Zones are drawn across the full
+/// width of the track, the value bar is inset from its sides by `bar_inset`. Threshold and
+/// target markers are drawn across the track on top.
+#[derive(Debug, bon::Builder)]
+pub struct LinearGauge<'a, C, const ZONES: usize = 0>
+where
+    C: Default,
+{
+    #[builder(default)]
+    value: i32,
+    #[builder(default)]
+    min: i32,
+    #[builder(default = 100)]
+    max: i32,
+    /// Horizontal gauges grow to the right, vertical ones upward
+    #[builder(default = Direction::Horizontal)]
+    direction: Direction,
+    #[builder(default = core::array::from_fn(|_| Zone::default()))]
+    zones: [Zone<C>; ZONES],
+    /// Values marked with a line across the track
+    #[builder(default = &[])]
+    thresholds: &'a [i32],
+    /// Value marked with a two pixel wide line, e.g. a goal to compare the value with
+    target: Option<i32>,
+    /// Color of the outline and the markers
+    #[builder(default)]
+    color: C,
+    #[builder(default)]
+    bar_color: C,
+    /// Pixels between the sides of the track and the value bar
+    #[builder(default)]
+    bar_inset: u32,
+}
+
+impl<C, const ZONES: usize> LinearGauge<'_, C, ZONES>
+where
+    C: Default,
+{
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value;
+    }
+
+    pub fn set_target(&mut self, target: Option<i32>) {
+        self.target = target;
+    }
+
+    pub fn zones_mut(&mut self) -> &mut [Zone<C>; ZONES] {
+        &mut self.zones
+    }
+
+    /// Pixels from the start of `track` up to `value`, values outside `min..=max` are clamped
+    fn offset(&self, track: &Rectangle, value: i32) -> i32 {
+        let length = match self.direction {
+            Direction::Horizontal => track.size.width,
+            Direction::Vertical => track.size.height,
+        } as i64;
+        let range = (self.max as i64 - self.min as i64).max(1);
+        let value = (value as i64 - self.min as i64).clamp(0, range);
+        (length * value / range) as i32
+    }
+
+    /// Part of `track` from `start` to `end`, shrunk by `inset` from the sides
+    fn span(&self, track: &Rectangle, start: i32, end: i32, inset: u32) -> Rectangle {
+        let (start, end) = (self.offset(track, start), self.offset(track, end));
+        let (start, end) = (start.min(end), start.max(end));
+        let inset = inset as i32;
+        let Some(bottom_right) = track.bottom_right() else {
+            return *track;
+        };
+        let (top_left, bottom_right) = match self.direction {
+            Direction::Horizontal => (
+                Point::new(track.top_left.x + start, track.top_left.y + inset),
+                Point::new(track.top_left.x + end - 1, bottom_right.y - inset),
+            ),
+            Direction::Vertical => (
+                Point::new(track.top_left.x + inset, bottom_right.y - end + 1),
+                Point::new(bottom_right.x - inset, bottom_right.y - start),
+            ),
+        };
+        if end == start || top_left.x > bottom_right.x || top_left.y > bottom_right.y {
+            return Rectangle::zero();
+        }
+        Rectangle::with_corners(top_left, bottom_right)
+    }
+
+    /// Line across `track` at `value`, `width` pixels wide and centered on the value
+    fn marker(&self, track: &Rectangle, value: i32, width: i32) -> Rectangle {
+        let offset = self.offset(track, value);
+        let start = offset - width / 2;
+        let Some(bottom_right) = track.bottom_right() else {
+            return *track;
+        };
+        let (top_left, bottom_right) = match self.direction {
+            Direction::Horizontal => (
+                Point::new(track.top_left.x + start, track.top_left.y),
+                Point::new(track.top_left.x + start + width - 1, bottom_right.y),
+            ),
+            Direction::Vertical => (
+                Point::new(track.top_left.x, bottom_right.y - start - width + 1),
+                Point::new(bottom_right.x, bottom_right.y - start),
+            ),
+        };
+        Rectangle::with_corners(top_left, bottom_right).intersection(track)
+    }
+}
+
+impl<C, const ZONES: usize> Drawable for LinearGauge<'_, C, ZONES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bounding_box = target.bounding_box();
+        bounding_box
+            .into_styled(PrimitiveStyle::with_stroke(self.color, 1))
+            .draw(target)?;
+        let track = bounding_box.offset(-1);
+
+        for zone in &self.zones {
+            target.fill_solid(&self.span(&track, zone.start, zone.end, 0), zone.color)?;
+        }
+        let bar = self.span(&track, self.min, self.value, self.bar_inset);
+        target.fill_solid(&bar, self.bar_color)?;
+        for threshold in self.thresholds {
+            target.fill_solid(&self.marker(&track, *threshold, 1), self.color)?;
+        }
+        if let Some(value) = self.target {
+            target.fill_solid(&self.marker(&track, value, 2), self.color)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{pixelcolor::BinaryColor, prelude::Size};
+
+    use super::*;
+
+    #[test]
+    fn test_angle_of_clamps_to_scale() {
+        let gauge = RadialGauge::<BinaryColor>::builder()
+            .min(-50)
+            .max(50)
+            .build();
+        let degrees = |value| (gauge.angle_of(value).to_degrees() + 0.5) as i32;
+        assert_eq!(
+            [135, 270, 405, 405],
+            [degrees(-80), degrees(0), degrees(50), degrees(60)]
+        );
+        assert_eq!(-30, gauge.tick_value(1));
+    }
+
+    #[test]
+    fn test_linear_gauge_spans_grow_upward() {
+        let gauge = LinearGauge::<BinaryColor>::builder()
+            .direction(Direction::Vertical)
+            .build();
+        let track = Rectangle::new(Point::new(1, 1), Size::new(6, 20));
+        assert_eq!(
+            Rectangle::with_corners(Point::new(3, 16), Point::new(4, 20)),
+            gauge.span(&track, 0, 25, 2)
+        );
+        assert_eq!(Rectangle::zero(), gauge.span(&track, 0, 0, 0));
+        assert_eq!(
+            Rectangle::with_corners(Point::new(1, 10), Point::new(6, 11)),
+            gauge.marker(&track, 50, 2)
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use embedded_graphics::{
+    prelude::{DrawTarget, PixelColor},
+    Drawable,
+};
+
+use crate::bar::BarChart;
+
+/// Counts samples in `BINS` equally wide bins and draws them with a [`BarChart`].
+///
+/// The bins cover `range` from its minimum to its maximum, both included. Samples outside it
+/// are counted as underflow or overflow. Without a configured range the first samples added
+/// set it, starting at their minimum and rounded up to a whole number of equally wide bins.
+/// Later samples outside it widen it by a whole multiple of the bin width, keeping one of the
+/// bin edges, so the count of each old bin falls in exactly one new bin.
+#[derive(Debug, bon::Builder)]
+pub struct Histogram<'a, C, const BINS: usize>
+where
+    C: Default,
+{
+    range: Option<(i32, i32)>,
+    /// Whether the range follows the samples, set when no range is configured
+    #[builder(skip = range.is_none())]
+    auto_range: bool,
+    #[builder(default = BarChart::builder().build())]
+    chart: BarChart<'a, C, BINS>,
+    #[builder(skip = [0; BINS])]
+    counts: [u32; BINS],
+    #[builder(skip)]
+    underflow: u32,
+    #[builder(skip)]
+    overflow: u32,
+}
+
+impl<'a, C, const BINS: usize> Histogram<'a, C, BINS>
+where
+    C: Default,
+{
+    pub fn add(&mut self, sample: i32) {
+        self.add_samples(&[sample]);
+    }
+
+    /// Adds `samples`, setting or widening the range to fit them if it is automatic
+    pub fn add_samples(&mut self, samples: &[i32]) {
+        let min = samples.iter().copied().min();
+        let max = samples.iter().copied().max();
+        if let (true, Some((min, max))) = (self.auto_range, min.zip(max)) {
+            self.widen(min, max);
+        }
+        for sample in samples {
+            match self.bin(*sample) {
+                Ok(bin) => self.counts[bin] = self.counts[bin].saturating_add(1),
+                Err(true) => self.underflow = self.underflow.saturating_add(1),
+                Err(false) => self.overflow = self.overflow.saturating_add(1),
+            }
+        }
+        self.update_chart();
+    }
+
+    /// Widens the automatic range to cover `min` to `max`. The bin width is multiplied by a
+    /// whole factor and the range starts at an old bin edge, moving each count exactly.
+    fn widen(&mut self, min: i32, max: i32) {
+        let bins = BINS as i64;
+        // Number of `width` wide steps needed to cover `length` values
+        let steps = |length: i64, width: i64| (length.max(0) + width - 1) / width;
+        let range = |start: i64, width: i64| {
+            let end = start + bins * width - 1;
+            Some((
+                start.max(i32::MIN.into()) as i32,
+                end.min(i32::MAX.into()) as i32,
+            ))
+        };
+        let Some((low, high)) = self.range else {
+            let length = max as i64 - min as i64 + 1;
+            self.range = range(min.into(), steps(length, bins).max(1));
+            return;
+        };
+        if low <= min && max <= high {
+            return;
+        }
+        let (low, high) = (low as i64, high as i64);
+        let width = (high - low + 1) / bins;
+        // Old bins the range is moved down by
+        let shift = steps(low - min as i64, width);
+        let start = low - shift * width;
+        let factor = steps(high.max(max.into()) - start + 1, bins * width);
+        let counts = core::mem::replace(&mut self.counts, [0; BINS]);
+        for (bin, count) in counts.into_iter().enumerate() {
+            let bin = ((bin as i64 + shift) / factor) as usize;
+            self.counts[bin] = self.counts[bin].saturating_add(count);
+        }
+        self.range = range(start, width * factor);
+    }
+
+    /// Bin of `sample`, `Err(true)` if it is below the range and `Err(false)` if above
+    fn bin(&self, sample: i32) -> Result<usize, bool> {
+        let Some((min, max)) = self.range else {
+            return Err(true);
+        };
+        if sample < min {
+            return Err(true);
+        }
+        if sample > max {
+            return Err(false);
+        }
+        let width = max as i64 - min as i64 + 1;
+        Ok(((sample as i64 - min as i64) * BINS as i64 / width) as usize)
+    }
+
+    /// Lowest and highest value counted in `bin`, `None` if there is no range yet or the range
+    /// is narrower than the number of bins and no value falls in `bin`
+    pub fn bin_range(&self, bin: usize) -> Option<(i32, i32)> {
+        let (min, max) = self.range?;
+        let width = (max as i64 - min as i64 + 1) as u64;
+        // Smallest offset from `min` that lands in `bin`
+        let start = |bin: u64| ((bin * width).div_ceil(BINS as u64)) as i64;
+        let (low, high) = (start(bin as u64), start(bin as u64 + 1) - 1);
+        (low <= high).then(|| ((min as i64 + low) as i32, (min as i64 + high) as i32))
+    }
+
+    pub fn counts(&self) -> &[u32; BINS] {
+        &self.counts
+    }
+
+    /// Samples below the range
+    pub fn underflow(&self) -> u32 {
+        self.underflow
+    }
+
+    /// Samples above the range
+    pub fn overflow(&self) -> u32 {
+        self.overflow
+    }
+
+    pub fn range(&self) -> Option<(i32, i32)> {
+        self.range
+    }
+
+    /// Sets the range and clears the counts. With `None` the range follows the next samples.
+    pub fn set_range(&mut self, range: Option<(i32, i32)>) {
+        self.range = range;
+        self.auto_range = range.is_none();
+        self.clear();
+    }
+
+    /// Clears the counts, keeping the range
+    pub fn clear(&mut self) {
+        self.counts = [0; BINS];
+        self.underflow = 0;
+        self.overflow = 0;
+        self.update_chart();
+    }
+
+    pub fn chart(&self) -> &BarChart<'a, C, BINS> {
+        &self.chart
+    }
+
+    /// The chart drawing the counts, e.g. to label the bins. Its values are overwritten when
+    /// the counts change.
+    pub fn chart_mut(&mut self) -> &mut BarChart<'a, C, BINS> {
+        &mut self.chart
+    }
+
+    fn update_chart(&mut self) {
+        let values = self.counts.map(|count| [count.min(i32::MAX as u32) as i32]);
+        self.chart.set_values(values);
+        self.chart.auto_range();
+    }
+}
+
+impl<C, const BINS: usize> Drawable for Histogram<'_, C, BINS>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.chart.draw(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    use super::*;
+
+    #[test]
+    fn test_bins_and_out_of_range_counts() {
+        let mut histogram = Histogram::<BinaryColor, 4>::builder()
+            .range((0, 99))
+            .build();
+        histogram.add_samples(&[-1, 0, 24, 25, 50, 99, 100, 120]);
+        assert_eq!(&[2, 1, 1, 1], histogram.counts());
+        assert_eq!((1, 2), (histogram.underflow(), histogram.overflow()));
+        assert_eq!(Some((25, 49)), histogram.bin_range(1));
+        assert_eq!(&[[2], [1], [1], [1]], histogram.chart().get_values());
+    }
+
+    #[test]
+    fn test_auto_range_from_first_samples() {
+        let mut histogram = Histogram::<BinaryColor, 3>::builder().build();
+        histogram.add_samples(&[10, 12, 15]);
+        assert_eq!(Some((10, 15)), histogram.range());
+        assert_eq!(&[1, 1, 1], histogram.counts());
+        histogram.add(9);
+        assert_eq!(Some((8, 19)), histogram.range());
+        assert_eq!(&[2, 2, 0], histogram.counts());
+        assert_eq!(0, histogram.underflow());
+    }
+
+    #[test]
+    fn test_auto_range_widens_with_single_samples() {
+        let mut histogram = Histogram::<BinaryColor, 3>::builder().build();
+        for sample in [10, 20, 30, 40] {
+            histogram.add(sample);
+        }
+        assert_eq!(Some((10, 57)), histogram.range());
+        assert_eq!(&[2, 2, 0], histogram.counts());
+        assert_eq!((0, 0), (histogram.underflow(), histogram.overflow()));
+    }
+
+    #[test]
+    fn test_widening_keeps_samples_at_bin_edges_in_their_bin() {
+        let samples = [0, 3, 6, 9, -1];
+        let mut widened = Histogram::<BinaryColor, 4>::builder().build();
+        for sample in samples {
+            widened.add(sample);
+        }
+        assert_eq!(Some((-4, 27)), widened.range());
+        let mut fixed = Histogram::<BinaryColor, 4>::builder()
+            .range((-4, 27))
+            .build();
+        fixed.add_samples(&samples);
+        assert_eq!(&[3, 2, 0, 0], fixed.counts());
+        assert_eq!(fixed.counts(), widened.counts());
+    }
+
+    #[test]
+    fn test_configured_range_does_not_widen() {
+        let mut histogram = Histogram::<BinaryColor, 2>::builder().range((0, 9)).build();
+        histogram.add(10);
+        assert_eq!(Some((0, 9)), histogram.range());
+        assert_eq!(1, histogram.overflow());
+    }
+}
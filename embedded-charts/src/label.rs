@@ -0,0 +1,95 @@
+use core::fmt;
+
+/// Where a label is drawn relative to the end of a bar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelPosition {
+    /// Past the end of the bar
+    #[default]
+    Outside,
+    /// Inside the bar, next to its end
+    Inside,
+}
+
+/// Label showing a value as text with [`crate::DEFAULT_FONT`].
+///
+/// Values are fixed point with `decimals` digits after the decimal point, so `1234` with two
+/// decimals reads `12.34`. The `unit` is written right after the number.
+#[derive(Debug, Clone, Copy, bon::Builder)]
+pub struct ValueLabel<'a, C> {
+    color: C,
+    /// Digits after the decimal point, at most 9
+    #[builder(default)]
+    decimals: u8,
+    #[builder(default = "")]
+    unit: &'a str,
+    #[builder(default)]
+    position: LabelPosition,
+}
+
+impl<'a, C: Copy> ValueLabel<'a, C> {
+    pub fn color(&self) -> C {
+        self.color
+    }
+
+    pub fn position(&self) -> LabelPosition {
+        self.position
+    }
+
+    /// `value` as it is written in the label
+    pub fn format(&self, value: i32) -> impl fmt::Display + 'a {
+        Formatted {
+            value,
+            decimals: self.decimals.min(9),
+            unit: self.unit,
+        }
+    }
+}
+
+struct Formatted<'a> {
+    value: i32,
+    decimals: u8,
+    unit: &'a str,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let magnitude = self.value.unsigned_abs();
+        if self.decimals == 0 {
+            return write!(f, "{sign}{magnitude}{}", self.unit);
+        }
+        let scale = 10u32.pow(self.decimals as u32);
+        write!(
+            f,
+            "{sign}{}.{:0width$}{}",
+            magnitude / scale,
+            magnitude % scale,
+            self.unit,
+            width = self.decimals as usize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(1234, 2, "V", "12.34V")]
+    #[test_case(-5, 1, "", "-0.5")]
+    #[test_case(7, 3, " mA", "0.007 mA")]
+    #[test_case(-42, 0, "C", "-42C")]
+    fn test_format(value: i32, decimals: u8, unit: &str, expected: &str) {
+        let label = ValueLabel::builder()
+            .color(())
+            .decimals(decimals)
+            .unit(unit)
+            .build();
+        assert_eq!(expected, label.format(value).to_string());
+    }
+}
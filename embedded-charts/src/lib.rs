@@ -1,39 +1,87 @@
 #![no_std]
-use embedded_graphics::prelude::{Point, Size};
-use u8g2_fonts::{
-    fonts::{u8g2_font_4x6_tf, u8g2_font_4x6_tn, u8g2_font_6x13_mr},
-    FontRenderer,
-};
+use embedded_graphics::prelude::{Dimensions, Point};
+use u8g2_fonts::{fonts::u8g2_font_4x6_tf, FontRenderer};
 
+use crate::axis::Axis;
+
+pub mod area;
 pub mod axis;
 pub mod bar;
 pub mod bar_line;
+pub mod box_plot;
+pub mod downsample;
+pub mod gauge;
+pub mod histogram;
+pub mod label;
 pub mod line;
+pub mod ohlc;
+pub mod pie;
 pub mod scatter;
+pub mod sparkline;
+mod spline;
+pub mod style;
+mod text;
+mod trig;
+pub mod trigger;
 
 pub const DEFAULT_FONT: u8g2_fonts::FontRenderer = FontRenderer::new::<u8g2_font_4x6_tf>();
 // pub const DEFAULT_FONT: u8g2_fonts::FontRenderer = FontRenderer::new::<u8g2_font_6x13_mr>();
 
-/// Scales point from chart scale to display scale (e.g. to pixel coordinates for drawing)
-pub fn scale_point(
-    p: Point,
-    display_size: &Size,
-    origin: &Point,
-    x_min: i32,
-    x_max: i32,
-    y_min: i32,
-    y_max: i32,
-) -> Point {
-    // TODO change arguments to some kind of struct config or similar to ease use
-    let new_x = scale_value(p.x, x_min, x_max, origin.x, display_size.width as i32);
-    let new_y = scale_value(
-        p.y,
-        y_min,
-        y_max,
-        display_size.height as i32 - origin.y,
-        display_size.height as i32,
-    );
-    Point::new(new_x, new_y)
+/// Conversion from chart coordinates (the data) to display coordinates (pixels).
+///
+/// Chart y grows upward while display y grows downward. [`Viewport::scale_point`] is the only
+/// place where the y axis is inverted, so the points it returns can be drawn as is.
+/// `(x_min, y_min)` lands on `origin` where the axes meet and `(x_max, y_max)` lands on
+/// the arrow tips of the axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    /// Display coordinates of `(x_min, y_min)`
+    pub origin: Point,
+    /// Display coordinates of `(x_max, y_max)`
+    pub end: Point,
+    pub x_min: i32,
+    pub x_max: i32,
+    pub y_min: i32,
+    pub y_max: i32,
+}
+
+impl Viewport {
+    /// Viewport spanning from the start of the axes to their arrow tips
+    pub fn from_axes<C, D>(x_axis: &Axis<C>, y_axis: &Axis<C>, display: &D) -> Self
+    where
+        C: Default,
+        D: Dimensions,
+    {
+        let origin = x_axis.calculate_starting_coordinates(display);
+        Self {
+            origin,
+            end: Point::new(
+                x_axis.calculate_tip_coordinates(display).x,
+                y_axis.calculate_tip_coordinates(display).y,
+            ),
+            x_min: x_axis.min,
+            x_max: x_axis.max,
+            y_min: y_axis.min,
+            y_max: y_axis.max,
+        }
+    }
+
+    /// Scales point from chart scale to display scale (e.g. to pixel coordinates for drawing)
+    pub fn scale_point(&self, p: Point) -> Point {
+        Point::new(
+            scale_value(p.x, self.x_min, self.x_max, self.origin.x, self.end.x),
+            scale_value(p.y, self.y_min, self.y_max, self.origin.y, self.end.y),
+        )
+    }
+
+    /// Like [`Viewport::scale_point`], but a point outside the viewport is first moved to its
+    /// closest edge instead of panicking
+    pub fn scale_point_clamped(&self, p: Point) -> Point {
+        self.scale_point(Point::new(
+            p.x.clamp(self.x_min, self.x_max),
+            p.y.clamp(self.y_min, self.y_max),
+        ))
+    }
 }
 
 /// Make sure
@@ -51,14 +99,81 @@ pub fn scale_value(value: i32, old_min: i32, old_max: i32, min: i32, max: i32) -
 #[cfg(test)]
 mod tests {
     // use super::*;
+    use embedded_graphics::{
+        prelude::{Point, Size},
+        primitives::Rectangle,
+    };
     use test_case::test_case;
 
-    use crate::scale_value;
+    use crate::{
+        axis::{calibrate_starting_points, Axis, Direction},
+        scale_value, Viewport,
+    };
 
     #[test_case(5,0,10,0,20,10 ; "5 from 0-10 to 0-20")]
-    #[test_case(50, 0, 10, 0, 160, 3)]
+    #[test_case(3, 0, 10, 0, 160, 48 ; "3 from 0-10 to 0-160")]
+    #[test_case(10, 0, 10, 61, 3, 3 ; "max lands on the end of a reversed range")]
     fn test_scale_value(value: i32, old_min: i32, old_max: i32, min: i32, max: i32, expected: i32) {
         let result = scale_value(value, old_min, old_max, min, max);
         assert_eq!(expected, result);
     }
+
+    fn calibrated_axes<'a>(display: &Rectangle) -> (Axis<'a, ()>, Axis<'a, ()>) {
+        let mut x_axis = Axis::builder().min(-10).max(50).show_legend(true).build();
+        let mut y_axis = Axis::builder()
+            .direction(Direction::Vertical)
+            .min(-5)
+            .max(36)
+            .show_legend(true)
+            .legend("y")
+            .build();
+        calibrate_starting_points(&mut x_axis, &mut y_axis, display).unwrap();
+        (x_axis, y_axis)
+    }
+
+    #[test]
+    fn test_y_min_lands_on_x_axis() {
+        let display = Rectangle::new(Point::zero(), Size::new(160, 128));
+        let (x_axis, y_axis) = calibrated_axes(&display);
+        let viewport = Viewport::from_axes(&x_axis, &y_axis, &display);
+
+        let p = viewport.scale_point(Point::new(20, y_axis.min));
+        assert_eq!(x_axis.calculate_starting_coordinates(&display).y, p.y);
+        let origin = viewport.scale_point(Point::new(x_axis.min, y_axis.min));
+        assert_eq!(y_axis.calculate_starting_coordinates(&display), origin);
+    }
+
+    #[test]
+    fn test_max_lands_on_arrow_tip() {
+        let display = Rectangle::new(Point::zero(), Size::new(160, 128));
+        let (x_axis, y_axis) = calibrated_axes(&display);
+        let viewport = Viewport::from_axes(&x_axis, &y_axis, &display);
+
+        let p = viewport.scale_point(Point::new(x_axis.max, y_axis.max));
+        assert_eq!(y_axis.calculate_tip_coordinates(&display).y, p.y);
+        assert_eq!(x_axis.calculate_tip_coordinates(&display).x, p.x);
+    }
+
+    #[test]
+    fn test_y_grows_upward() {
+        let display = Rectangle::new(Point::zero(), Size::new(160, 128));
+        let (x_axis, y_axis) = calibrated_axes(&display);
+        let viewport = Viewport::from_axes(&x_axis, &y_axis, &display);
+
+        let low = viewport.scale_point(Point::new(0, 0));
+        let high = viewport.scale_point(Point::new(0, 10));
+        assert!(high.y < low.y);
+    }
+
+    #[test]
+    fn test_clamped_point_lands_on_closest_edge() {
+        let display = Rectangle::new(Point::zero(), Size::new(160, 128));
+        let (x_axis, y_axis) = calibrated_axes(&display);
+        let viewport = Viewport::from_axes(&x_axis, &y_axis, &display);
+
+        let p = viewport.scale_point_clamped(Point::new(20, 150));
+        assert_eq!(viewport.scale_point(Point::new(20, y_axis.max)), p);
+        let p = viewport.scale_point_clamped(Point::new(-100, -100));
+        assert_eq!(viewport.origin, p);
+    }
 }
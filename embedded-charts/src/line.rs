@@ -1,23 +1,325 @@
 #![allow(dead_code)]
-use bon::builder;
-use embedded_graphics::draw_target::DrawTarget;
-use embedded_graphics::prelude::{Dimensions, PixelColor, Point, Primitive, Size};
-use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle};
+use embedded_graphics::draw_target::{DrawTarget, DrawTargetExt};
+use embedded_graphics::prelude::{Dimensions, PixelColor, Point, Primitive};
+use embedded_graphics::primitives::{Circle, Line, PrimitiveStyle, Rectangle};
+use embedded_graphics::transform::Transform;
 use embedded_graphics::Drawable;
+use u8g2_fonts::types::{HorizontalAlignment, VerticalPosition};
 
+use crate::area::{fill_between, Area};
 use crate::axis::Axis;
-use crate::scale_point;
+use crate::downsample::lttb_by;
+use crate::label::ValueLabel;
+use crate::spline::monotone_path;
+use crate::style::{DashPattern, LineStyle, Stroke};
+use crate::trigger::Trigger;
+use crate::Viewport;
 
 // TODO should the Line own the data (points) or should it take reference?
 // This leads to lifetime questions
 
+/// Pattern used for [`GapMode::Dashed`]
+const GAP_PATTERN: DashPattern<'static> = DashPattern::new(&[3, 3]);
+
+/// How missing samples (`None`) between two present samples are drawn.
+///
+/// Missing samples before the first or after the last present sample are never drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GapMode {
+    /// Leave the gap empty, the line resumes at the next present sample.
+    #[default]
+    Break,
+    /// Connect the samples on both sides of the gap with a solid line.
+    Bridge,
+    /// Connect the samples on both sides of the gap with a dashed line.
+    Dashed,
+}
+
+/// Line between two drawn samples in display coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Segment {
+    start: Point,
+    end: Point,
+    /// Segment bridges a gap with [`GapMode::Dashed`]
+    dashed: bool,
+    /// Point connected to `start` on the other side, used for smoothing
+    before: Option<Point>,
+    /// Point connected to `end` on the other side, used for smoothing
+    after: Option<Point>,
+}
+
+/// Segments connecting `points` in order, following `gap_mode` over missing samples. The gap
+/// after `break_after` is always left empty.
+fn segments(
+    points: &[Option<Point>],
+    gap_mode: GapMode,
+    break_after: Option<Point>,
+) -> impl Iterator<Item = Segment> + '_ {
+    let bridged = move |start: Option<Point>| gap_mode != GapMode::Break && start != break_after;
+    // Closest present point after index `i`, if it is connected to the point at `i`
+    let next = move |i: usize| {
+        let (j, p) = (i + 1..points.len()).find_map(|j| points[j].map(|p| (j, p)))?;
+        let gap = j > i + 1;
+        (!gap || bridged(points[i])).then_some((j, p, gap))
+    };
+    let previous = move |i: usize| {
+        let (j, p) = (0..i).rev().find_map(|j| points[j].map(|p| (j, p)))?;
+        (j + 1 == i || bridged(Some(p))).then_some(p)
+    };
+    points.iter().enumerate().filter_map(move |(i, point)| {
+        let start = (*point)?;
+        let (j, end, gap) = next(i)?;
+        Some(Segment {
+            start,
+            end,
+            dashed: gap && gap_mode == GapMode::Dashed,
+            before: previous(i),
+            after: next(j).map(|(_, p, _)| p),
+        })
+    })
+}
+
+/// Vertical bar per display column spanning the lowest and highest of the `points` in it.
+///
+/// Peak preserving decimation, a bar also reaches to the last point of the previous column
+/// so neighbouring bars stay connected. Missing samples break the connection.
+fn envelope(points: &[Option<Point>]) -> impl Iterator<Item = Segment> + '_ {
+    let mut column: Option<Segment> = None;
+    let mut previous_y: Option<i32> = None;
+    // `None` at the end flushes the last column
+    points
+        .iter()
+        .map(Some)
+        .chain(core::iter::once(None))
+        .filter_map(move |point| match point {
+            Some(Some(p)) => {
+                let finished = match &mut column {
+                    Some(c) if c.start.x == p.x => {
+                        c.start.y = c.start.y.min(p.y);
+                        c.end.y = c.end.y.max(p.y);
+                        None
+                    }
+                    _ => {
+                        let from = previous_y.unwrap_or(p.y);
+                        column.replace(Segment {
+                            start: Point::new(p.x, from.min(p.y)),
+                            end: Point::new(p.x, from.max(p.y)),
+                            ..Segment::default()
+                        })
+                    }
+                };
+                previous_y = Some(p.y);
+                finished
+            }
+            Some(None) => {
+                previous_y = None;
+                column.take()
+            }
+            None => column.take(),
+        })
+}
+
+/// How the points are reduced before drawing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Decimation {
+    /// Every point is drawn
+    #[default]
+    None,
+    /// One vertical min-max bar per pixel column. Draw time is bounded
+    /// by the plot width instead of the number of samples and spikes are never lost.
+    /// Only the latest point gets a marker.
+    MinMax,
+    /// Downsample to about `points` points with [`lttb`](crate::downsample::lttb), keeping the shape of the line
+    Lttb { points: usize },
+}
+
+/// [`lttb`](crate::downsample::lttb) applied to every run of present points separately so gaps stay in place.
+///
+/// Runs share `threshold` by their length. The kept points are packed to the start of the
+/// returned array, runs separated by a single `None`.
+fn lttb_runs<const N: usize>(points: &[Option<Point>; N], threshold: usize) -> [Option<Point>; N] {
+    let present = points.iter().flatten().count();
+    if present <= threshold {
+        return *points;
+    }
+    let mut out = [None; N];
+    let mut written = 0;
+    for run in points.split(Option::is_none).filter(|run| !run.is_empty()) {
+        if written > 0 {
+            // Leave a `None` in between to keep the gap
+            written += 1;
+        }
+        let share = (run.len() * threshold).div_ceil(present);
+        lttb_by(
+            run.len(),
+            |i| run[i].unwrap(),
+            share,
+            |p| {
+                out[written] = Some(p);
+                written += 1;
+            },
+        );
+    }
+    out
+}
+
+/// How the line goes from one sample to the next
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Straight line between the samples
+    #[default]
+    Linear,
+    /// The value changes at the left sample and holds until the right one
+    StepBefore,
+    /// The value holds from the left sample and changes at the right one, like sample and hold
+    StepAfter,
+    /// The value changes halfway between the samples
+    StepMiddle,
+    /// Smooth monotone cubic curve that never overshoots the samples, so it stays inside
+    /// the axis bounds
+    Monotone,
+}
+
+/// Horizontal distance in pixels between the points of a [`Interpolation::Monotone`] curve
+const SMOOTH_STEP: u32 = 2;
+
+/// Straight pieces drawn for `segment` with `interpolation`, in order from start to end
+fn pieces(segment: Segment, interpolation: Interpolation) -> impl Iterator<Item = Segment> {
+    let Segment {
+        start,
+        end,
+        dashed,
+        before,
+        after,
+    } = segment;
+    let (left, right) = if start.x <= end.x {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    let corners = match interpolation {
+        Interpolation::Linear | Interpolation::Monotone => [None, None],
+        Interpolation::StepBefore => [Some(Point::new(left.x, right.y)), None],
+        Interpolation::StepAfter => [Some(Point::new(right.x, left.y)), None],
+        Interpolation::StepMiddle => {
+            let middle = (start.x + end.x) / 2;
+            [
+                Some(Point::new(middle, start.y)),
+                Some(Point::new(middle, end.y)),
+            ]
+        }
+    };
+    let corner_path = (interpolation != Interpolation::Monotone).then(|| {
+        [Some(start), corners[0], corners[1], Some(end)]
+            .into_iter()
+            .flatten()
+    });
+    let curve_path = (interpolation == Interpolation::Monotone)
+        .then(|| monotone_path(before, start, end, after, SMOOTH_STEP));
+    corner_path
+        .into_iter()
+        .flatten()
+        .chain(curve_path.into_iter().flatten())
+        .scan(None, move |previous: &mut Option<Point>, p| {
+            let piece = previous.replace(p).map(|s| Segment {
+                start: s,
+                end: p,
+                dashed,
+                ..Segment::default()
+            });
+            Some(piece)
+        })
+        .flatten()
+        .filter(move |piece| piece.start != piece.end || start == end)
+}
+
+/// Strokes of one drawing pass over the segments of a chart, keeping the dash patterns
+/// continuous from segment to segment
+struct SeriesStroke<'a, C> {
+    line: Stroke<'a, C>,
+    /// For segments bridging a gap with [`GapMode::Dashed`]
+    gap: Stroke<'a, C>,
+    interpolation: Interpolation,
+}
+
+impl<C: PixelColor> SeriesStroke<'_, C> {
+    fn draw<D>(&mut self, target: &mut D, segment: Segment) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        for piece in pieces(segment, self.interpolation) {
+            match piece.dashed {
+                true => self.gap.draw_line(target, piece.start, piece.end)?,
+                false => self.line.draw_line(target, piece.start, piece.end)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the patterns in phase without drawing `segment`
+    fn skip(&mut self, segment: Segment) {
+        for piece in pieces(segment, self.interpolation) {
+            match piece.dashed {
+                true => self.gap.skip_line(piece.start, piece.end),
+                false => self.line.skip_line(piece.start, piece.end),
+            }
+        }
+    }
+}
+
+/// How new samples are placed on the chart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// New samples are added to the front and the oldest one drops out, the line scrolls.
+    #[default]
+    Scroll,
+    /// New samples overwrite the chart from left to right and wrap around, like on an
+    /// oscilloscope. The x coordinate of pushed points is ignored, each sample is placed
+    /// by its slot in the buffer. `blank` slots ahead of the write position are cleared so
+    /// the old and new sweep are separated by a moving erase bar.
+    Sweep { blank: usize },
+}
+
+/// Vertical bars from the lower to the upper bound of each sample, see
+/// [`LineChart::push_with_error`]
+#[derive(Debug, Clone, Copy, bon::Builder)]
+pub struct ErrorBars<C> {
+    color: C,
+    /// Width of the caps at the ends of the bars in pixels, 0 for no caps
+    #[builder(default = 3)]
+    cap_width: u32,
+}
+
+impl<C: Copy> ErrorBars<C> {
+    pub fn color(&self) -> C {
+        self.color
+    }
+
+    pub fn cap_width(&self) -> u32 {
+        self.cap_width
+    }
+}
+
+/// Points, latest point, value tag area and value and trigger level drawn by
+/// [`LineChart::redraw`]
+type DrawnState<const SAMPLES: usize> = (
+    [Option<Point>; SAMPLES],
+    Option<Point>,
+    Option<(Rectangle, i32)>,
+    Option<i32>,
+);
+
 #[derive(Debug, bon::Builder)]
 pub struct LineChart<'a, C, const SAMPLES: usize = 5>
 where
     C: Default,
 {
-    #[builder(default = [None; SAMPLES], with = |points:[Point;SAMPLES]| points.map(|p| Some(p)))]
+    #[builder(default = [None; SAMPLES], with = |points:[Point;SAMPLES]| points.map(Some))]
     points: [Option<Point>; SAMPLES],
+    /// Buffer keeping the lower and upper bound of each sample in `points`, e.g.
+    /// `&mut [None; SAMPLES]`. It is borrowed so charts without bounds do not store them,
+    /// without it the bounds of pushed samples are dropped.
+    bounds: Option<&'a mut [Option<(i32, i32)>; SAMPLES]>,
     #[builder(default = Axis::default_x_axis())]
     x_axis: Axis<'a, C>,
     #[builder(default = Axis::default_y_axis())]
@@ -28,10 +330,43 @@ where
     point_color: C,
     #[builder(default = 3)]
     line_width: u32,
+    #[builder(default)]
+    line_style: LineStyle<'a>,
+    #[builder(default)]
+    interpolation: Interpolation,
+    /// Fill between the line and a baseline
+    area: Option<Area<C>>,
+    /// Fill between the lower and upper bounds of the samples, behind the line
+    band_color: Option<C>,
+    /// Bars from the lower to the upper bound of every sample
+    error_bars: Option<ErrorBars<C>>,
+    /// Writes the value of the latest sample next to it
+    value_tag: Option<ValueLabel<'a, C>>,
     #[builder(default = 5)]
     point_diameter: u32,
     #[builder(default = true)]
     show_points: bool,
+    /// Only draw the marker of the latest sample when `show_points` is set
+    #[builder(default)]
+    latest_point_only: bool,
+    #[builder(default)]
+    gap_mode: GapMode,
+    #[builder(default)]
+    mode: Mode,
+    #[builder(default)]
+    decimation: Decimation,
+    /// Only show complete captures started by the trigger instead of the latest samples
+    trigger: Option<Trigger<SAMPLES>>,
+    /// Ignore new samples, keeping the chart frozen
+    #[builder(default)]
+    hold: bool,
+    /// Slot the next sample is written to in [`Mode::Sweep`]
+    #[builder(skip)]
+    cursor: usize,
+    /// Display coordinates of the points drawn by the last [`LineChart::redraw`], the
+    /// latest of them, the area and value of the value tag and the trigger level
+    #[builder(skip)]
+    drawn: Option<DrawnState<SAMPLES>>,
 }
 
 impl<C, const SAMPLES: usize> LineChart<'_, C, SAMPLES>
@@ -39,30 +374,123 @@ where
     C: Default,
 {
     pub fn push(&mut self, new_point: Point) {
-        self.points.rotate_right(1);
-        self.points[0] = Some(new_point);
+        self.push_sample(Some(new_point), None);
+    }
+
+    /// Adds a missing sample, drawn as a gap following the [`GapMode`]. Missing samples are
+    /// not fed to the trigger.
+    pub fn push_missing(&mut self) {
+        self.push_sample(None, None);
+    }
+
+    /// Adds a sample with an error of `below` under and `above` over its y value
+    pub fn push_with_error(&mut self, new_point: Point, below: i32, above: i32) {
+        self.push_with_bounds(new_point, new_point.y - below, new_point.y + above);
+    }
+
+    /// Adds a sample with a lower and upper bound, e.g. the mean with the mean plus and minus
+    /// the standard deviation. Bounds are only kept with a `bounds` buffer and not for trigger
+    /// captures.
+    pub fn push_with_bounds(&mut self, new_point: Point, low: i32, high: i32) {
+        self.push_sample(Some(new_point), Some((low.min(high), low.max(high))));
+    }
+
+    fn push_sample(&mut self, new_point: Option<Point>, bounds: Option<(i32, i32)>) {
+        if self.hold {
+            return;
+        }
+        if let Some(trigger) = &mut self.trigger {
+            if let Some(capture) = new_point.and_then(|p| trigger.feed(p.y)) {
+                self.show_capture(capture);
+            }
+            return;
+        }
+        match self.mode {
+            Mode::Scroll => {
+                self.points.rotate_right(1);
+                self.points[0] = new_point;
+                if let Some(stored) = self.bounds.as_deref_mut() {
+                    stored.rotate_right(1);
+                    stored[0] = bounds;
+                }
+            }
+            Mode::Sweep { blank } => {
+                let x = self.slot_x(self.cursor);
+                self.points[self.cursor] = new_point.map(|p| Point::new(x, p.y));
+                if let Some(stored) = self.bounds.as_deref_mut() {
+                    stored[self.cursor] = bounds;
+                }
+                self.cursor = (self.cursor + 1) % SAMPLES;
+                for i in 0..blank.min(SAMPLES - 1) {
+                    let slot = (self.cursor + i) % SAMPLES;
+                    self.points[slot] = None;
+                    if let Some(stored) = self.bounds.as_deref_mut() {
+                        stored[slot] = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replaces the points with a trigger capture spread over the whole x axis
+    fn show_capture(&mut self, capture: [Option<i32>; SAMPLES]) {
+        for (slot, value) in capture.into_iter().enumerate() {
+            let index = match self.mode {
+                Mode::Scroll => SAMPLES - 1 - slot,
+                Mode::Sweep { .. } => slot,
+            };
+            self.points[index] = value.map(|y| Point::new(self.slot_x(slot), y));
+        }
+        if let Some(stored) = self.bounds.as_deref_mut() {
+            *stored = [None; SAMPLES];
+        }
+        self.cursor = 0;
+    }
+
+    pub fn trigger(&self) -> Option<&Trigger<SAMPLES>> {
+        self.trigger.as_ref()
+    }
+
+    pub fn trigger_mut(&mut self) -> Option<&mut Trigger<SAMPLES>> {
+        self.trigger.as_mut()
+    }
+
+    /// Freezes the chart, new samples are ignored until released
+    pub fn set_hold(&mut self, hold: bool) {
+        self.hold = hold;
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.hold
+    }
+
+    /// Index of the latest sample in [`LineChart::get_points`]
+    pub fn latest(&self) -> usize {
+        match self.mode {
+            Mode::Scroll => 0,
+            Mode::Sweep { .. } => (self.cursor + SAMPLES - 1) % SAMPLES,
+        }
+    }
+
+    /// Chart x coordinate of a slot in [`Mode::Sweep`] and trigger captures, slots are spread evenly over the x axis
+    fn slot_x(&self, slot: usize) -> i32 {
+        let range = self.x_axis.max - self.x_axis.min;
+        self.x_axis.min + slot as i32 * range / (SAMPLES as i32 - 1).max(1)
     }
 
     pub fn get_points(&self) -> &[Option<Point>] {
         &self.points
     }
 
-    /// Scale points to display coordinates. Should be called before drawing.
-    /// Or optionally scale the data before inserting outside of this struct
-    pub fn scale_points_to_display<D: Dimensions>(&mut self, display: &D) {
-        self.points = self.points.map(|point| match point {
-            Some(p) => Some(scale_point(
-                p,
-                &display.bounding_box().size,
-                &self.x_axis.calculate_starting_coordinates(display),
-                // &self.x_axis.origin_offset,
-                self.x_axis.min,
-                self.x_axis.max,
-                self.y_axis.min,
-                self.y_axis.max,
-            )),
-            None => None,
-        });
+    /// Lower and upper bound of each sample in [`LineChart::get_points`], empty without a
+    /// `bounds` buffer
+    pub fn get_bounds(&self) -> &[Option<(i32, i32)>] {
+        self.bounds.as_deref().map_or(&[], |stored| stored)
+    }
+
+    /// Viewport used to scale the points for drawing on `display`
+    pub fn viewport<D: Dimensions>(&self, display: &D) -> Viewport {
+        Viewport::from_axes(&self.x_axis, &self.y_axis, display)
     }
 }
 
@@ -72,6 +500,484 @@ impl<C: Default> Default for LineChart<'_, C> {
     }
 }
 
+impl<'a, C, const SAMPLES: usize> LineChart<'a, C, SAMPLES>
+where
+    C: PixelColor + Default,
+{
+    /// Redraws only what changed since the previous call, without clearing the display.
+    ///
+    /// Segments and markers that are no longer part of the chart are erased by drawing them
+    /// again with `background`, then the new ones are drawn. With dashes every segment after
+    /// the first changed one is redrawn to keep the pattern in phase. Drawing is clipped to
+    /// the area inside the axes so the axes are left untouched, except when the trigger level
+    /// marker moves and the axes are drawn again over the erased marker. The first call draws
+    /// the whole chart. With an [`Area`] fill, a band or error bars the whole plot area is
+    /// cleared and drawn again.
+    pub fn redraw<D>(&mut self, target: &mut D, background: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let viewport = self.viewport(target);
+        let (points, latest) = self.display_points(target);
+        let tag = latest
+            .and_then(|p| self.value_tag_placement(&viewport, p))
+            .map(|(_, _, area, value)| (area, value));
+        let level = self.trigger.as_ref().map(Trigger::level);
+        let Some((drawn, drawn_latest, drawn_tag, drawn_level)) =
+            self.drawn.replace((points, latest, tag, level))
+        else {
+            return self.draw(target);
+        };
+        if drawn_level != level {
+            self.redraw_trigger_marker(target, drawn_level, background)?;
+        }
+        let plot_area = self.plot_area(target);
+        if self.area.is_some() || self.band_color.is_some() || self.error_bars.is_some() {
+            target.fill_solid(&plot_area, background)?;
+            return self.draw_series(&mut target.clipped(&plot_area), &viewport, &points, latest);
+        }
+        if let Mode::Sweep { blank } = self.mode {
+            self.clear_erase_bar(target, &plot_area, blank, background)?;
+        }
+        let mut target = target.clipped(&plot_area);
+
+        // Area of the old value tag if it has to go
+        let stale_tag = drawn_tag
+            .filter(|old| Some(*old) != tag)
+            .map(|(area, _)| area);
+        if let Some(area) = stale_tag {
+            target.fill_solid(&area, background)?;
+        }
+        let under_stale_tag =
+            |area: Rectangle| stale_tag.is_some_and(|tag| !tag.intersection(&area).is_zero_sized());
+
+        // Dash patterns continue from segment to segment, so with dashes every segment after
+        // the first changed one is drawn again to stay in phase with a fresh draw
+        let dashed =
+            matches!(self.line_style, LineStyle::Dashed(_)) || self.gap_mode == GapMode::Dashed;
+        let in_phase = match dashed {
+            true => self
+                .lines(&drawn, drawn_latest)
+                .zip(self.lines(&points, latest))
+                .take_while(|(old, new)| old == new)
+                .count(),
+            false => usize::MAX,
+        };
+        let mut stale_segments = [false; SAMPLES];
+        // Dashes are erased with the pattern and phase they were drawn with, a solid line
+        // does not cover the ends of short thick dashes
+        let mut eraser =
+            self.series_stroke(self.line_style, LineStyle::Dashed(GAP_PATTERN), background);
+        for (i, old) in self.lines(&drawn, drawn_latest).enumerate() {
+            if i >= in_phase || !self.lines(&points, latest).any(|new| new == old) {
+                stale_segments[i] = true;
+                eraser.draw(&mut target, old)?;
+            } else {
+                eraser.skip(old);
+            }
+        }
+        let mut stale_markers = [false; SAMPLES];
+        if self.show_points {
+            for (i, old) in self.markers(&drawn, drawn_latest).enumerate() {
+                if !self.markers(&points, latest).any(|new| new == old) {
+                    stale_markers[i] = true;
+                    self.draw_marker(&mut target, old, background)?;
+                }
+            }
+        }
+
+        // Erasing may have cut into the neighbours of erased segments and markers
+        let erased = |p: Point| {
+            self.lines(&drawn, drawn_latest)
+                .zip(stale_segments)
+                .any(|(s, stale)| stale && (s.start == p || s.end == p))
+                || self
+                    .markers(&drawn, drawn_latest)
+                    .zip(stale_markers)
+                    .any(|(m, stale)| stale && m == p)
+        };
+        let mut stroke = self.series_stroke(
+            self.line_style,
+            LineStyle::Dashed(GAP_PATTERN),
+            self.line_color,
+        );
+        for new in self.lines(&points, latest) {
+            if erased(new.start)
+                || erased(new.end)
+                || under_stale_tag(Rectangle::with_corners(new.start, new.end))
+                || !self.lines(&drawn, drawn_latest).any(|old| old == new)
+            {
+                stroke.draw(&mut target, new)?;
+            } else {
+                stroke.skip(new);
+            }
+        }
+        if self.show_points {
+            for new in self.markers(&points, latest) {
+                if erased(new)
+                    || under_stale_tag(Circle::with_center(new, self.point_diameter).bounding_box())
+                    || !self.markers(&drawn, drawn_latest).any(|old| old == new)
+                {
+                    self.draw_marker(&mut target, new, self.point_color)?;
+                }
+            }
+        }
+        // The line may have been drawn over the tag
+        self.draw_value_tag(&mut target, &viewport, latest)
+    }
+
+    /// Draws the axes and the trigger level marker
+    fn draw_axes<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.x_axis.draw(target)?;
+        self.y_axis.draw(target)?;
+        if let Some(trigger) = &self.trigger {
+            self.y_axis
+                .draw_value_marker(target, trigger.level(), self.line_color)?;
+        }
+        Ok(())
+    }
+
+    /// Erases the trigger level marker drawn at `old` and draws the axes and the marker at
+    /// the current level again, as the marker can overlap the axes
+    fn redraw_trigger_marker<D>(
+        &self,
+        target: &mut D,
+        old: Option<i32>,
+        background: C,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some(old) = old {
+            self.y_axis.draw_value_marker(target, old, background)?;
+        }
+        self.draw_axes(target)
+    }
+
+    /// Area above the x axis and right of the y axis, the series is not drawn outside it so
+    /// [`LineChart::redraw`] can erase everything it drew
+    pub fn plot_area<D: Dimensions>(&self, display: &D) -> Rectangle {
+        let viewport = self.viewport(display);
+        let bounding_box = display.bounding_box();
+        Rectangle::with_corners(
+            Point::new(
+                viewport.origin.x + self.y_axis.clearance(),
+                bounding_box.top_left.y,
+            ),
+            Point::new(
+                bounding_box.bottom_right().map_or(viewport.end.x, |p| p.x),
+                viewport.origin.y - self.x_axis.clearance(),
+            ),
+        )
+    }
+
+    /// Points in display coordinates, downsampled for [`Decimation::Lttb`], and the latest point
+    fn display_points<D: Dimensions>(
+        &self,
+        display: &D,
+    ) -> ([Option<Point>; SAMPLES], Option<Point>) {
+        let viewport = self.viewport(display);
+        let points = self
+            .points
+            .map(|p| p.map(|p| viewport.scale_point_clamped(p)));
+        let latest = points[self.latest()];
+        match self.decimation {
+            Decimation::Lttb { points: threshold } => (lttb_runs(&points, threshold), latest),
+            Decimation::None | Decimation::MinMax => (points, latest),
+        }
+    }
+
+    /// Lines drawn for `points` following the gap mode and decimation. In [`Mode::Sweep`] the
+    /// erase bar after the `latest` point is never bridged.
+    fn lines<'p>(
+        &self,
+        points: &'p [Option<Point>],
+        latest: Option<Point>,
+    ) -> impl Iterator<Item = Segment> + 'p {
+        let erase_bar = matches!(self.mode, Mode::Sweep { blank } if blank > 0);
+        let break_after = latest.filter(|_| erase_bar);
+        let envelope_columns = (self.decimation == Decimation::MinMax).then(|| envelope(points));
+        let segments = (self.decimation != Decimation::MinMax)
+            .then(|| segments(points, self.gap_mode, break_after));
+        segments
+            .into_iter()
+            .flatten()
+            .chain(envelope_columns.into_iter().flatten())
+    }
+
+    /// Points that get a marker, every present point once or only the latest one if
+    /// `latest_point_only` is set or the points are decimated to an envelope
+    fn markers<'p>(
+        &self,
+        points: &'p [Option<Point>],
+        latest: Option<Point>,
+    ) -> impl Iterator<Item = Point> + 'p {
+        let latest_only = self.latest_point_only || self.decimation == Decimation::MinMax;
+        let all = (!latest_only).then(|| points.iter().flatten().copied());
+        all.into_iter()
+            .flatten()
+            .chain(latest.filter(|_| latest_only))
+    }
+
+    /// Fills the columns of the `blank` slots ahead of the write position in [`Mode::Sweep`]
+    fn clear_erase_bar<D>(
+        &self,
+        target: &mut D,
+        plot_area: &Rectangle,
+        blank: usize,
+        background: C,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let viewport = self.viewport(target);
+        let column_x = |slot: usize| {
+            viewport
+                .scale_point(Point::new(self.slot_x(slot), viewport.y_min))
+                .x
+        };
+        for slot in (self.cursor..self.cursor + blank.min(SAMPLES - 1)).map(|i| i % SAMPLES) {
+            let right = if slot + 1 < SAMPLES {
+                column_x(slot + 1) - 1
+            } else {
+                plot_area.bottom_right().map_or(viewport.end.x, |p| p.x)
+            };
+            let column = Rectangle::with_corners(
+                Point::new(column_x(slot), plot_area.top_left.y),
+                Point::new(right.max(column_x(slot)), viewport.origin.y),
+            )
+            .intersection(plot_area);
+            target.fill_solid(&column, background)?;
+        }
+        Ok(())
+    }
+
+    fn series_stroke(
+        &self,
+        line_style: LineStyle<'a>,
+        gap_style: LineStyle<'a>,
+        color: C,
+    ) -> SeriesStroke<'a, C> {
+        SeriesStroke {
+            line: Stroke::new(line_style, color, self.line_width),
+            gap: Stroke::new(gap_style, color, self.line_width),
+            interpolation: self.effective_interpolation(),
+        }
+    }
+
+    fn effective_interpolation(&self) -> Interpolation {
+        // Envelope columns are already vertical lines
+        match self.decimation {
+            Decimation::MinMax => Interpolation::Linear,
+            Decimation::None | Decimation::Lttb { .. } => self.interpolation,
+        }
+    }
+
+    /// Draws the band, the area fill, the line, the error bars and the markers of `points` in
+    /// display coordinates.
+    /// `viewport` is the one of the whole display, `target` may be clipped.
+    fn draw_series<D>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+        points: &[Option<Point>],
+        latest: Option<Point>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some(color) = self.band_color {
+            self.draw_band(target, viewport, color)?;
+        }
+        if let Some(area) = &self.area {
+            self.draw_area(target, viewport, points, latest, area)?;
+        }
+        if self.area.is_none_or(|area| area.outline()) {
+            let mut stroke = self.series_stroke(
+                self.line_style,
+                LineStyle::Dashed(GAP_PATTERN),
+                self.line_color,
+            );
+            for segment in self.lines(points, latest) {
+                stroke.draw(target, segment)?;
+            }
+        }
+        if let Some(error_bars) = &self.error_bars {
+            self.draw_error_bars(target, viewport, error_bars)?;
+        }
+        // Markers are drawn after the line so they stay on top
+        if self.show_points {
+            for point in self.markers(points, latest) {
+                self.draw_marker(target, point, self.point_color)?;
+            }
+        }
+        self.draw_value_tag(target, viewport, latest)
+    }
+
+    /// Where the value tag of the `latest` point is drawn: its anchor, alignment, the area it
+    /// covers and the value written. The tag is right of the point, or left of it when there
+    /// is no room, and kept inside the plot area.
+    fn value_tag_placement(
+        &self,
+        viewport: &Viewport,
+        latest: Point,
+    ) -> Option<(Point, HorizontalAlignment, Rectangle, i32)> {
+        let label = self.value_tag.as_ref()?;
+        let value = self.points[self.latest()]?.y;
+        let gap = if self.show_points {
+            self.point_diameter as i32 / 2 + 2
+        } else {
+            2
+        };
+        let area = |position: Point, alignment: HorizontalAlignment| {
+            crate::DEFAULT_FONT
+                .get_rendered_dimensions_aligned(
+                    format_args!("{}", label.format(value)),
+                    position,
+                    VerticalPosition::Center,
+                    alignment,
+                )
+                .ok()
+                .flatten()
+        };
+        let mut position = latest + Point::new(gap, 0);
+        let mut alignment = HorizontalAlignment::Left;
+        let mut covered = area(position, alignment)?;
+        if covered.bottom_right()?.x > viewport.end.x {
+            position = latest - Point::new(gap, 0);
+            alignment = HorizontalAlignment::Right;
+            covered = area(position, alignment)?;
+        }
+        let bottom = viewport.origin.y - self.x_axis.clearance();
+        let shift = (viewport.end.y - covered.top_left.y).max(0)
+            + (bottom - covered.bottom_right()?.y).min(0);
+        let shift = Point::new(0, shift);
+        Some((position + shift, alignment, covered.translate(shift), value))
+    }
+
+    fn draw_value_tag<D>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+        latest: Option<Point>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let (Some(label), Some(latest)) = (&self.value_tag, latest) else {
+            return Ok(());
+        };
+        let Some((position, alignment, _, value)) = self.value_tag_placement(viewport, latest)
+        else {
+            return Ok(());
+        };
+        crate::text::draw(
+            target,
+            format_args!("{}", label.format(value)),
+            position,
+            VerticalPosition::Center,
+            alignment,
+            label.color(),
+        )
+    }
+
+    /// Fills between the line and the baseline of `area`, gaps are left empty
+    fn draw_area<D>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+        points: &[Option<Point>],
+        latest: Option<Point>,
+        area: &Area<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        // The fill stops above the x axis line
+        let baseline_y = viewport
+            .scale_point_clamped(Point::new(viewport.x_min, area.baseline()))
+            .y
+            .min(viewport.origin.y - self.x_axis.clearance());
+        let interpolation = self.effective_interpolation();
+        for segment in self.lines(points, latest).filter(|s| !s.dashed) {
+            for piece in pieces(segment, interpolation) {
+                let base = (
+                    Point::new(piece.start.x, baseline_y),
+                    Point::new(piece.end.x, baseline_y),
+                );
+                fill_between(target, (piece.start, piece.end), base, area.color())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Display coordinates of the bounds of the sample at `index`, kept above the x axis line
+    fn bound_points(&self, viewport: &Viewport, index: usize) -> Option<(Point, Point)> {
+        let bounds = self.bounds.as_deref()?;
+        let (point, (low, high)) = self.points[index].zip(bounds[index])?;
+        let bottom = viewport.origin.y - self.x_axis.clearance();
+        let scale = |value: i32| {
+            let p = viewport.scale_point_clamped(Point::new(point.x, value));
+            Point::new(p.x, p.y.min(bottom))
+        };
+        Some((scale(low), scale(high)))
+    }
+
+    /// Fills between the bounds of neighbouring samples, a sample without bounds leaves a gap.
+    /// The band follows the samples, not the decimated line.
+    fn draw_band<D>(&self, target: &mut D, viewport: &Viewport, color: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        for i in 1..SAMPLES {
+            let (Some((low0, high0)), Some((low1, high1))) = (
+                self.bound_points(viewport, i - 1),
+                self.bound_points(viewport, i),
+            ) else {
+                continue;
+            };
+            fill_between(target, (high0, high1), (low0, low1), color)?;
+        }
+        Ok(())
+    }
+
+    fn draw_error_bars<D>(
+        &self,
+        target: &mut D,
+        viewport: &Viewport,
+        error_bars: &ErrorBars<C>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let style = PrimitiveStyle::with_stroke(error_bars.color(), 1);
+        let half_cap = Point::new(error_bars.cap_width() as i32 / 2, 0);
+        for (low, high) in (0..SAMPLES).filter_map(|i| self.bound_points(viewport, i)) {
+            Line::new(low, high).into_styled(style).draw(target)?;
+            if error_bars.cap_width() > 0 {
+                for end in [low, high] {
+                    Line::new(end - half_cap, end + half_cap)
+                        .into_styled(style)
+                        .draw(target)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_marker<D>(&self, target: &mut D, point: Point, color: C) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        Circle::with_center(point, self.point_diameter)
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(target)
+    }
+}
+
 impl<C, const SAMPLES: usize> Drawable for LineChart<'_, C, SAMPLES>
 where
     C: PixelColor + Default,
@@ -83,39 +989,421 @@ where
     where
         D: DrawTarget<Color = Self::Color>,
     {
-        self.x_axis.draw(target)?;
-        self.y_axis.draw(target)?;
-        let height = target.bounding_box().size.height;
-        for w in self.points.windows(2) {
-            if let (Some(mut p1), Some(mut p2)) = (w[0], w[1]) {
-                p1 = Point {
-                    y: height as i32 - p1.y,
-                    ..p1
-                };
-                p2 = Point {
-                    y: height as i32 - p2.y,
-                    ..p2
-                };
-                if self.show_points {
-                    Circle::with_center(p1, self.point_diameter)
-                        .into_styled(PrimitiveStyle::with_fill(self.point_color))
-                        .draw(target)?;
-                    // TODO figure out better way to draw the last point instead of drawing other points 2 times
-                    Circle::with_center(p2, self.point_diameter)
-                        .into_styled(PrimitiveStyle::with_fill(self.point_color))
-                        .draw(target)?;
-                }
+        self.draw_axes(target)?;
 
-                Line::new(p1, p2)
-                    .into_styled(PrimitiveStyle::with_stroke(
-                        self.line_color,
-                        self.line_width,
-                    ))
-                    .draw(target)?;
-            } else {
-                break;
+        let viewport = self.viewport(target);
+        let (points, latest) = self.display_points(target);
+        let plot_area = self.plot_area(target);
+        self.draw_series(&mut target.clipped(&plot_area), &viewport, &points, latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        prelude::{OriginDimensions, Size},
+        primitives::{PointsIter, Rectangle},
+        Pixel,
+    };
+    use test_case::test_case;
+
+    use super::*;
+    use crate::axis::Direction;
+
+    fn y_axis(max: i32) -> Axis<'static, BinaryColor> {
+        Axis::builder()
+            .direction(Direction::Vertical)
+            .max(max)
+            .color(BinaryColor::On)
+            .build()
+    }
+
+    /// Axes with one unit per pixel on the 64 by 64 mock display, chart point `(x, y)` is
+    /// drawn at `(3 + x, 61 - y)`
+    fn pixel_axes() -> (Axis<'static, BinaryColor>, Axis<'static, BinaryColor>) {
+        (
+            Axis::builder().max(58).build(),
+            Axis::builder()
+                .direction(Direction::Vertical)
+                .max(58)
+                .build(),
+        )
+    }
+
+    /// Pixels of `display` inside `area`, moved to the top left corner to match a small pattern
+    fn crop(display: &MockDisplay<BinaryColor>, area: Rectangle) -> MockDisplay<BinaryColor> {
+        let mut cropped = MockDisplay::new();
+        let pixels = area
+            .points()
+            .filter_map(|p| display.get_pixel(p).map(|c| Pixel(p - area.top_left, c)));
+        cropped.draw_iter(pixels).unwrap();
+        cropped
+    }
+
+    #[test_case(GapMode::Break, "####        ###"; "gap left empty")]
+    #[test_case(GapMode::Bridge, "###############"; "gap bridged")]
+    #[test_case(GapMode::Dashed, "######   ######"; "gap dashed")]
+    fn test_line_over_gap(gap_mode: GapMode, expected: &str) {
+        let (x_axis, y_axis) = pixel_axes();
+        let mut chart = LineChart::<BinaryColor>::builder()
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .line_color(BinaryColor::On)
+            .line_width(1)
+            .show_points(false)
+            .gap_mode(gap_mode)
+            .build();
+        chart.push(Point::new(21, 50));
+        chart.push(Point::new(19, 50));
+        chart.push_missing();
+        chart.push(Point::new(10, 50));
+        chart.push(Point::new(7, 50));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+        let line = Rectangle::new(Point::new(10, 11), Size::new(15, 1));
+        crop(&display, line).assert_pattern(&[expected]);
+    }
+
+    /// Counts the pixels drawn inside `area`, to catch pixels drawn more than once
+    struct PixelCounter {
+        area: Rectangle,
+        count: usize,
+    }
+
+    impl OriginDimensions for PixelCounter {
+        fn size(&self) -> Size {
+            Size::new(64, 64)
+        }
+    }
+
+    impl DrawTarget for PixelCounter {
+        type Color = BinaryColor;
+        type Error = core::convert::Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            let area = self.area;
+            self.count += pixels.into_iter().filter(|p| area.contains(p.0)).count();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_marker_for_every_point_once() {
+        let (x_axis, y_axis) = pixel_axes();
+        let mut chart = LineChart::<BinaryColor, 3>::builder()
+            .points([8, 0, 12].map(|x| Point::new(x, 50)))
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .point_color(BinaryColor::On)
+            .point_diameter(3)
+            .build();
+        chart.points[1] = None;
+        let markers = Rectangle::new(Point::new(10, 10), Size::new(7, 3));
+        // Lone points get a marker too
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+        crop(&display, markers).assert_pattern(&[
+            " #   # ", //
+            "### ###", //
+            " #   # ",
+        ]);
+
+        let mut counter = PixelCounter {
+            area: markers,
+            count: 0,
+        };
+        chart.draw(&mut counter).unwrap();
+        assert_eq!(10, counter.count);
+    }
+
+    #[test]
+    fn test_latest_point_only() {
+        let (x_axis, y_axis) = pixel_axes();
+        let mut chart = LineChart::<BinaryColor, 3>::builder()
+            .points([12, 0, 8].map(|x| Point::new(x, 50)))
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .point_color(BinaryColor::On)
+            .point_diameter(3)
+            .latest_point_only(true)
+            .build();
+        chart.points[1] = None;
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+        let markers = Rectangle::new(Point::new(10, 10), Size::new(7, 3));
+        crop(&display, markers).assert_pattern(&[
+            "     # ", //
+            "    ###", //
+            "     # ",
+        ]);
+    }
+
+    #[test_case(LineStyle::Solid, Mode::Scroll; "solid")]
+    #[test_case(LineStyle::Dashed(DashPattern::DASHED), Mode::Scroll; "dashed")]
+    #[test_case(LineStyle::Dashed(DashPattern::DASHED), Mode::Sweep { blank: 1 }; "dashed sweep")]
+    fn test_redraw_matches_fresh_draw(line_style: LineStyle<'static>, mode: Mode) {
+        let mut chart = LineChart::<BinaryColor, 10>::builder()
+            .x_axis(Axis::builder().max(14).color(BinaryColor::On).build())
+            .y_axis(y_axis(60))
+            .line_color(BinaryColor::On)
+            .point_color(BinaryColor::On)
+            .line_style(line_style)
+            .gap_mode(GapMode::Dashed)
+            .mode(mode)
+            .build();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display
+            .fill_solid(&display.bounding_box(), BinaryColor::Off)
+            .unwrap();
+        for x in 0..4 {
+            chart.push(Point::new(x, 0));
+        }
+        chart.redraw(&mut display, BinaryColor::Off).unwrap();
+        for x in 4..14 {
+            match x {
+                6 => chart.push_missing(),
+                _ => chart.push(Point::new(x, x * 4)),
             }
+            chart.redraw(&mut display, BinaryColor::Off).unwrap();
         }
-        Ok(())
+
+        let mut fresh = MockDisplay::new();
+        fresh.set_allow_overdraw(true);
+        fresh
+            .fill_solid(&fresh.bounding_box(), BinaryColor::Off)
+            .unwrap();
+        chart.draw(&mut fresh).unwrap();
+        display.assert_eq(&fresh);
+    }
+
+    #[test]
+    fn test_redraw_moves_trigger_marker() {
+        let mut chart = LineChart::<BinaryColor, 10>::builder()
+            .x_axis(Axis::builder().max(10).color(BinaryColor::On).build())
+            .y_axis(y_axis(60))
+            .line_color(BinaryColor::On)
+            .trigger(crate::trigger::Trigger::builder().level(50).build())
+            .build();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        display
+            .fill_solid(&display.bounding_box(), BinaryColor::Off)
+            .unwrap();
+        chart.redraw(&mut display, BinaryColor::Off).unwrap();
+        chart.trigger_mut().unwrap().set_level(10);
+        chart.redraw(&mut display, BinaryColor::Off).unwrap();
+
+        let mut fresh = MockDisplay::new();
+        fresh.set_allow_overdraw(true);
+        fresh
+            .fill_solid(&fresh.bounding_box(), BinaryColor::Off)
+            .unwrap();
+        chart.draw(&mut fresh).unwrap();
+        display.assert_eq(&fresh);
+    }
+
+    fn sweep_chart(blank: usize, samples: i32) -> LineChart<'static, BinaryColor, 5> {
+        let mut chart = LineChart::<BinaryColor, 5>::builder()
+            .x_axis(Axis::builder().max(4).build())
+            .mode(Mode::Sweep { blank })
+            .gap_mode(GapMode::Bridge)
+            .build();
+        for y in 1..=samples {
+            chart.push(Point::new(0, y));
+        }
+        chart
+    }
+
+    #[test]
+    fn test_sweep_wraps_and_blanks_ahead() {
+        let chart = sweep_chart(2, 6);
+        assert_eq!(
+            &[
+                Some(Point::new(0, 6)),
+                None,
+                None,
+                Some(Point::new(3, 4)),
+                Some(Point::new(4, 5))
+            ],
+            chart.get_points()
+        );
+        assert_eq!(0, chart.latest());
+    }
+
+    #[test]
+    fn test_sweep_never_bridges_erase_bar() {
+        let chart = sweep_chart(2, 6);
+        let latest = chart.get_points()[chart.latest()];
+        let lines = chart
+            .lines(chart.get_points(), latest)
+            .map(|s| (s.start, s.end));
+        assert!(lines.eq([(Point::new(3, 4), Point::new(4, 5))]));
+
+        // Gaps that are not the erase bar follow the gap mode
+        let points = [Some(Point::new(0, 1)), None, Some(Point::new(2, 1))];
+        let lines = chart.lines(&points, None).map(|s| (s.start, s.end));
+        assert!(lines.eq([(Point::new(0, 1), Point::new(2, 1))]));
+    }
+
+    #[test]
+    fn test_erase_bar_wraps_to_first_column() {
+        // Slots 4 and 0 are blank
+        let chart = sweep_chart(2, 4);
+        let mut display = MockDisplay::new();
+        let plot_area = chart.plot_area(&display);
+        chart
+            .clear_erase_bar(&mut display, &plot_area, 2, BinaryColor::Off)
+            .unwrap();
+        let viewport = chart.viewport(&display);
+        let column = |slot: i32| viewport.scale_point(Point::new(slot, 0)).x;
+        let y = plot_area.center().y;
+        let right = plot_area.bottom_right().unwrap().x;
+        for (x, cleared) in [
+            (plot_area.top_left.x, true),
+            (column(1) - 1, true),
+            (column(1), false),
+            (column(4) - 1, false),
+            (column(4), true),
+            (right, true),
+        ] {
+            let expected = cleared.then_some(BinaryColor::Off);
+            assert_eq!(expected, display.get_pixel(Point::new(x, y)), "x = {x}");
+        }
+    }
+
+    #[test]
+    fn test_envelope_marks_latest_point_only() {
+        let chart = LineChart::<BinaryColor>::builder()
+            .decimation(Decimation::MinMax)
+            .build();
+        let points = [1, 2, 3].map(|x| Some(Point::new(x, 5)));
+        let latest = points[0];
+        assert!(chart.markers(&points, latest).eq(latest));
+    }
+
+    #[test]
+    fn test_envelope_keeps_peaks_and_connects_columns() {
+        let points = [
+            Some(Point::new(0, 5)),
+            Some(Point::new(0, 9)),
+            Some(Point::new(0, 1)),
+            Some(Point::new(0, 4)),
+            Some(Point::new(1, 6)),
+            None,
+            Some(Point::new(2, 3)),
+        ];
+        let columns: [(i32, i32, i32); 3] = [(0, 1, 9), (1, 4, 6), (2, 3, 3)];
+        let mut envelope = envelope(&points);
+        for (x, low, high) in columns {
+            let column = envelope.next().unwrap();
+            assert_eq!(
+                (Point::new(x, low), Point::new(x, high)),
+                (column.start, column.end)
+            );
+        }
+        assert_eq!(None, envelope.next());
+    }
+
+    #[test]
+    fn test_bounds_follow_their_samples() {
+        let mut bounds = [None; 3];
+        let mut chart = LineChart::<(), 3>::builder().bounds(&mut bounds).build();
+        chart.push_with_error(Point::new(0, 5), 1, 2);
+        chart.push(Point::new(1, 6));
+        chart.push_with_bounds(Point::new(2, 7), 9, 4);
+        assert_eq!(&[Some((4, 9)), None, Some((4, 7))], chart.get_bounds());
+    }
+
+    #[test]
+    fn test_sample_outside_axes_drawn_at_edge() {
+        let mut chart = LineChart::<BinaryColor>::builder()
+            .line_color(BinaryColor::On)
+            .build();
+        chart.push(Point::new(1, 150));
+        chart.push(Point::new(-5, -20));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+    }
+
+    #[test]
+    fn test_missing_sample_takes_a_sweep_slot() {
+        let mut bounds = [None; 3];
+        let mut chart = LineChart::<(), 3>::builder()
+            .x_axis(Axis::builder().max(2).build())
+            .mode(Mode::Sweep { blank: 0 })
+            .bounds(&mut bounds)
+            .build();
+        for y in [5, 6, 7] {
+            chart.push_with_error(Point::new(0, y), 1, 1);
+        }
+        chart.push_missing();
+        chart.push(Point::new(0, 8));
+        assert_eq!(
+            &[None, Some(Point::new(1, 8)), Some(Point::new(2, 7))],
+            chart.get_points()
+        );
+        assert_eq!(&[None, None, Some((6, 8))], chart.get_bounds());
+        assert_eq!(1, chart.latest());
+    }
+
+    #[test]
+    fn test_step_pieces() {
+        let segment = Segment {
+            start: Point::new(10, 2),
+            end: Point::new(0, 6),
+            ..Segment::default()
+        };
+        let corners = |interpolation| {
+            let mut corners = [Point::zero(); 3];
+            for (corner, piece) in corners.iter_mut().zip(pieces(segment, interpolation)) {
+                *corner = piece.end;
+            }
+            corners
+        };
+        assert_eq!(
+            [Point::new(10, 6), Point::new(0, 6), Point::zero()],
+            corners(Interpolation::StepAfter)
+        );
+        assert_eq!(
+            [Point::new(0, 2), Point::new(0, 6), Point::zero()],
+            corners(Interpolation::StepBefore)
+        );
+        assert_eq!(
+            [Point::new(5, 2), Point::new(5, 6), Point::new(0, 6)],
+            corners(Interpolation::StepMiddle)
+        );
+    }
+
+    #[test]
+    fn test_area_fills_to_baseline_on_both_sides() {
+        let mut chart = LineChart::<BinaryColor, 2>::builder()
+            .x_axis(Axis::builder().max(10).build())
+            .y_axis(y_axis(100))
+            .area(Area::builder().color(BinaryColor::On).baseline(50).build())
+            .show_points(false)
+            .build();
+        chart.push(Point::new(0, 0));
+        chart.push(Point::new(10, 100));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        chart.draw(&mut display).unwrap();
+
+        let viewport = chart.viewport(&display);
+        let filled = |x: i32, y: i32| {
+            display.get_pixel(viewport.scale_point(Point::new(x, y))) == Some(BinaryColor::On)
+        };
+        // Below the baseline left of the crossing, above it right of the crossing
+        assert!(filled(2, 40) && !filled(2, 60));
+        assert!(filled(8, 60) && !filled(8, 40));
     }
 }
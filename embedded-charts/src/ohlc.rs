@@ -0,0 +1,240 @@
+use embedded_graphics::{
+    prelude::{Dimensions, DrawTarget, PixelColor, Point},
+    primitives::{Line, Primitive, PrimitiveStyle, Rectangle},
+    Drawable,
+};
+
+use crate::axis::Axis;
+use crate::Viewport;
+
+/// Open, high, low and close value of an interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candle {
+    pub open: i32,
+    pub high: i32,
+    pub low: i32,
+    pub close: i32,
+}
+
+impl Candle {
+    /// Candle of a single sample
+    pub fn new(value: i32) -> Self {
+        Self {
+            open: value,
+            high: value,
+            low: value,
+            close: value,
+        }
+    }
+
+    /// Adds a later sample
+    pub fn update(&mut self, value: i32) {
+        self.high = self.high.max(value);
+        self.low = self.low.min(value);
+        self.close = value;
+    }
+
+    /// Whether the interval closed at or above its open
+    pub fn is_up(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+/// Summarises samples into a [`Candle`] per `interval` of x.
+///
+/// Intervals start at multiples of `interval`, so samples at `x` from 60 to 119 with an
+/// interval of 60 go into the candle at 60.
+#[derive(Debug, bon::Builder)]
+pub struct CandleAggregator {
+    interval: i32,
+    #[builder(skip)]
+    current: Option<(i32, Candle)>,
+}
+
+impl CandleAggregator {
+    /// Adds a sample at `x`, returning the candle of the previous interval once `x` is past it
+    pub fn push(&mut self, x: i32, value: i32) -> Option<(i32, Candle)> {
+        let interval = self.interval.max(1);
+        let start = x.div_euclid(interval) * interval;
+        match &mut self.current {
+            Some((current, candle)) if *current == start => {
+                candle.update(value);
+                None
+            }
+            _ => self.current.replace((start, Candle::new(value))),
+        }
+    }
+
+    /// Candle of the interval still being collected
+    pub fn current(&self) -> Option<(i32, Candle)> {
+        self.current
+    }
+
+    /// Returns the candle of the interval being collected and starts over
+    pub fn flush(&mut self) -> Option<(i32, Candle)> {
+        self.current.take()
+    }
+}
+
+/// How a candle is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CandleStyle {
+    /// Filled body from open to close with a wick from low to high
+    #[default]
+    Candlestick,
+    /// Line from low to high with the open marked on the left and the close on the right
+    Ohlc,
+}
+
+/// Chart of the last `CANDLES` candles, each placed at the x where its interval starts
+#[derive(Debug, bon::Builder)]
+pub struct CandlestickChart<'a, C, const CANDLES: usize>
+where
+    C: Default,
+{
+    #[builder(skip = [None; CANDLES])]
+    candles: [Option<(i32, Candle)>; CANDLES],
+    #[builder(default = Axis::default_x_axis())]
+    x_axis: Axis<'a, C>,
+    #[builder(default = Axis::default_y_axis())]
+    y_axis: Axis<'a, C>,
+    /// Color of candles closing at or above their open
+    #[builder(default)]
+    up_color: C,
+    /// Color of candles closing below their open
+    #[builder(default)]
+    down_color: C,
+    #[builder(default)]
+    style: CandleStyle,
+    /// Width of a candle in pixels
+    #[builder(default = 3)]
+    width: u32,
+}
+
+impl<C, const CANDLES: usize> CandlestickChart<'_, C, CANDLES>
+where
+    C: Default,
+{
+    /// Adds a candle at `x`, dropping the oldest one when the chart is full. A candle at the
+    /// same `x` as the newest one replaces it, so the interval still being collected can be
+    /// shown while it grows.
+    pub fn push(&mut self, x: i32, candle: Candle) {
+        match &mut self.candles[0] {
+            Some((newest, newest_candle)) if *newest == x => *newest_candle = candle,
+            _ => {
+                self.candles.rotate_right(1);
+                self.candles[0] = Some((x, candle));
+            }
+        }
+    }
+
+    /// Candles, newest first
+    pub fn get_candles(&self) -> &[Option<(i32, Candle)>; CANDLES] {
+        &self.candles
+    }
+
+    /// Sets the y axis range from the lowest low to the highest high
+    pub fn auto_range(&mut self) {
+        let candles = self.candles.iter().flatten();
+        let low = candles.clone().map(|(_, candle)| candle.low).min();
+        let high = candles.map(|(_, candle)| candle.high).max();
+        if let (Some(low), Some(high)) = (low, high) {
+            self.y_axis.fit_range(low, high);
+        }
+    }
+
+    /// Viewport mapping candle times and prices onto `display`
+    pub fn viewport<D: Dimensions>(&self, display: &D) -> Viewport {
+        Viewport::from_axes(&self.x_axis, &self.y_axis, display)
+    }
+}
+
+impl<C, const CANDLES: usize> Drawable for CandlestickChart<'_, C, CANDLES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let viewport = self.viewport(target);
+        let half_width = self.width as i32 / 2;
+        for (x, candle) in self.candles.iter().flatten() {
+            if !(viewport.x_min..=viewport.x_max).contains(x) {
+                continue;
+            }
+            let scale = |value: i32| viewport.scale_point_clamped(Point::new(*x, value));
+            let color = if candle.is_up() {
+                self.up_color
+            } else {
+                self.down_color
+            };
+            let style = PrimitiveStyle::with_stroke(color, 1);
+            Line::new(scale(candle.high), scale(candle.low))
+                .into_styled(style)
+                .draw(target)?;
+            let (open, close) = (scale(candle.open), scale(candle.close));
+            match self.style {
+                CandleStyle::Candlestick => {
+                    let offset = Point::new(half_width, 0);
+                    Rectangle::with_corners(open - offset, close + offset)
+                        .into_styled(PrimitiveStyle::with_fill(color))
+                        .draw(target)?;
+                }
+                CandleStyle::Ohlc => {
+                    let offset = Point::new(half_width.max(1), 0);
+                    Line::new(open - offset, open)
+                        .into_styled(style)
+                        .draw(target)?;
+                    Line::new(close, close + offset)
+                        .into_styled(style)
+                        .draw(target)?;
+                }
+            }
+        }
+
+        // Axes on top, so candles at the ends of the range do not cover them
+        self.x_axis.draw(target)?;
+        self.y_axis.draw(target)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    use super::*;
+
+    #[test]
+    fn test_aggregator_closes_candle_on_next_interval() {
+        let mut aggregator = CandleAggregator::builder().interval(60).build();
+        assert_eq!(None, aggregator.push(60, 5));
+        assert_eq!(None, aggregator.push(80, 9));
+        assert_eq!(None, aggregator.push(119, 2));
+        let candle = Candle {
+            open: 5,
+            high: 9,
+            low: 2,
+            close: 2,
+        };
+        assert_eq!(Some((60, candle)), aggregator.push(120, 4));
+        assert!(!candle.is_up());
+        assert_eq!(Some((120, Candle::new(4))), aggregator.flush());
+    }
+
+    #[test]
+    fn test_push_replaces_candle_at_same_x() {
+        let mut chart = CandlestickChart::<BinaryColor, 3>::builder().build();
+        chart.push(0, Candle::new(1));
+        chart.push(1, Candle::new(2));
+        chart.push(1, Candle::new(3));
+        assert_eq!(
+            &[Some((1, Candle::new(3))), Some((0, Candle::new(1))), None],
+            chart.get_candles()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use embedded_graphics::{
+    geometry::Angle,
+    prelude::{DrawTarget, PixelColor, Primitive},
+    primitives::{Arc, PrimitiveStyle, PrimitiveStyleBuilder, Sector, StrokeAlignment},
+    Drawable,
+};
+use u8g2_fonts::types::{HorizontalAlignment, VerticalPosition};
+
+use crate::trig::point_at;
+
+/// Pie chart of `SLICES` values, each slice as large as its share of the total.
+///
+/// The chart is the largest circle fitting the display, slices follow each other clockwise
+/// from `start_angle`. With a `hole_diameter` it is drawn as a donut.
+#[derive(Debug, bon::Builder)]
+pub struct PieChart<C, const SLICES: usize>
+where
+    C: Default,
+{
+    #[builder(default = [0; SLICES])]
+    values: [u32; SLICES],
+    /// Color of each slice
+    #[builder(default = core::array::from_fn(|_| C::default()))]
+    colors: [C; SLICES],
+    /// Where the first slice starts, measured clockwise from 3 o'clock. Defaults to 12 o'clock.
+    #[builder(default = Angle::from_degrees(-90.0))]
+    start_angle: Angle,
+    /// Diameter of the empty center, 0 for a pie
+    #[builder(default)]
+    hole_diameter: u32,
+    /// Pixels each slice is moved out from the center, separating it from its neighbours
+    #[builder(default)]
+    separation: u32,
+    /// Writes the percentage of each slice on it in this color
+    label_color: Option<C>,
+}
+
+impl<C, const SLICES: usize> PieChart<C, SLICES>
+where
+    C: Default,
+{
+    pub fn get_values(&self) -> &[u32; SLICES] {
+        &self.values
+    }
+
+    pub fn set_values(&mut self, values: [u32; SLICES]) {
+        self.values = values;
+    }
+
+    pub fn set(&mut self, slice: usize, value: u32) {
+        self.values[slice] = value;
+    }
+
+    fn total(&self) -> u64 {
+        self.values.iter().map(|value| *value as u64).sum()
+    }
+
+    /// Share of `slice` in the total in percent, rounded
+    pub fn percentage(&self, slice: usize) -> u32 {
+        let total = self.total();
+        if total == 0 {
+            return 0;
+        }
+        ((self.values[slice] as u64 * 200 + total) / (total * 2)) as u32
+    }
+
+    /// Start and sweep angle of `slice`. Slices start at the running total so rounding does
+    /// not add up to a gap.
+    fn slice_angles(&self, slice: usize) -> (Angle, Angle) {
+        let total = self.total().max(1) as f32;
+        let before: u64 = self.values[..slice].iter().map(|v| *v as u64).sum();
+        let degrees = |value: u64| value as f32 * 360.0 / total;
+        let start = degrees(before);
+        let end = degrees(before + self.values[slice] as u64);
+        (
+            self.start_angle + Angle::from_degrees(start),
+            Angle::from_degrees(end - start),
+        )
+    }
+}
+
+impl<C, const SLICES: usize> Drawable for PieChart<C, SLICES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bounding_box = target.bounding_box();
+        let center = bounding_box.center();
+        let diameter = bounding_box
+            .size
+            .width
+            .min(bounding_box.size.height)
+            .saturating_sub(2 * self.separation + 1);
+        let hole_diameter = self.hole_diameter.min(diameter);
+        let ring_width = (diameter - hole_diameter) / 2;
+
+        for (slice, color) in self.colors.iter().enumerate() {
+            if self.values[slice] == 0 {
+                continue;
+            }
+            let (start, sweep) = self.slice_angles(slice);
+            let middle = start + Angle::from_degrees(sweep.to_degrees() / 2.0);
+            let slice_center = point_at(center, self.separation as i32, middle);
+            if hole_diameter == 0 {
+                Sector::with_center(slice_center, diameter, start, sweep)
+                    .into_styled(PrimitiveStyle::with_fill(*color))
+                    .draw(target)?;
+            } else {
+                let style = PrimitiveStyleBuilder::new()
+                    .stroke_color(*color)
+                    .stroke_width(ring_width)
+                    .stroke_alignment(StrokeAlignment::Inside)
+                    .build();
+                Arc::with_center(slice_center, diameter, start, sweep)
+                    .into_styled(style)
+                    .draw(target)?;
+            }
+        }
+
+        let Some(label_color) = self.label_color else {
+            return Ok(());
+        };
+        // Labels in the middle of the ring, or two thirds out on a pie
+        let label_radius = if hole_diameter == 0 {
+            diameter as i32 / 3
+        } else {
+            (diameter + hole_diameter) as i32 / 4
+        };
+        for slice in 0..SLICES {
+            if self.values[slice] == 0 {
+                continue;
+            }
+            let (start, sweep) = self.slice_angles(slice);
+            let middle = start + Angle::from_degrees(sweep.to_degrees() / 2.0);
+            let position = point_at(center, label_radius + self.separation as i32, middle);
+            crate::text::draw(
+                target,
+                format_args!("{}%", self.percentage(slice)),
+                position,
+                VerticalPosition::Center,
+                HorizontalAlignment::Center,
+                label_color,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    use super::*;
+
+    #[test]
+    fn test_percentages_and_slice_angles() {
+        let pie = PieChart::<BinaryColor, 3>::builder()
+            .values([1, 1, 2])
+            .build();
+        assert_eq!(
+            [25, 25, 50],
+            [pie.percentage(0), pie.percentage(1), pie.percentage(2)]
+        );
+        let (start, sweep) = pie.slice_angles(2);
+        assert_eq!((90.0, 180.0), (start.to_degrees(), sweep.to_degrees()));
+    }
+}
@@ -0,0 +1,175 @@
+use core::cmp::Reverse;
+
+use embedded_graphics::{
+    prelude::{DrawTarget, PixelColor, Point, Primitive},
+    primitives::{Circle, Line, PrimitiveStyle},
+    Drawable,
+};
+
+use crate::Viewport;
+
+/// Line of the last `SAMPLES` values without axes or legend, filling the whole display.
+///
+/// Meant for a small area next to a readout, e.g. a 40x12 pixel `cropped` part of the
+/// display. The oldest value is at the left edge and the newest at the right edge. Without a
+/// `range` the line spans from the smallest to the largest value shown.
+#[derive(Debug, bon::Builder)]
+pub struct Sparkline<C, const SAMPLES: usize>
+where
+    C: Default,
+{
+    #[builder(skip = [None; SAMPLES])]
+    values: [Option<i32>; SAMPLES],
+    /// Fixed range of the values, values outside it are clamped
+    range: Option<(i32, i32)>,
+    #[builder(default)]
+    line_color: C,
+    /// Marks the smallest and largest value with a dot
+    min_max_color: Option<C>,
+    /// Marks the newest value with a dot
+    last_color: Option<C>,
+    #[builder(default = 3)]
+    dot_diameter: u32,
+}
+
+impl<C, const SAMPLES: usize> Sparkline<C, SAMPLES>
+where
+    C: Default,
+{
+    /// Adds a value, dropping the oldest one when the sparkline is full
+    pub fn push(&mut self, value: i32) {
+        self.values.rotate_right(1);
+        self.values[0] = Some(value);
+    }
+
+    /// Values, newest first
+    pub fn get_values(&self) -> &[Option<i32>; SAMPLES] {
+        &self.values
+    }
+
+    pub fn clear(&mut self) {
+        self.values = [None; SAMPLES];
+    }
+
+    /// Range the values are drawn in, the configured one or the extent of the values.
+    /// `None` without a range and values.
+    pub fn range(&self) -> Option<(i32, i32)> {
+        if self.range.is_some() {
+            return self.range;
+        }
+        let min = *self.values.iter().flatten().min()?;
+        let max = *self.values.iter().flatten().max()?;
+        Some((min, max))
+    }
+
+    /// Indices of the oldest smallest and the oldest largest value
+    fn extremes(&self) -> Option<(usize, usize)> {
+        let present = || {
+            self.values
+                .iter()
+                .enumerate()
+                .rev()
+                .filter_map(|(i, value)| value.map(|value| (i, value)))
+        };
+        let (min, _) = present().min_by_key(|(_, value)| *value)?;
+        let (max, _) = present().min_by_key(|(_, value)| Reverse(*value))?;
+        Some((min, max))
+    }
+}
+
+impl<C, const SAMPLES: usize> Drawable for Sparkline<C, SAMPLES>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let Some((min, max)) = self.range() else {
+            return Ok(());
+        };
+        let bounding_box = target.bounding_box();
+        let Some(bottom_right) = bounding_box.bottom_right() else {
+            return Ok(());
+        };
+        // Room for half a dot at the edges
+        let margin = if self.min_max_color.is_some() || self.last_color.is_some() {
+            self.dot_diameter as i32 / 2
+        } else {
+            0
+        };
+        let viewport = Viewport {
+            origin: Point::new(bounding_box.top_left.x + margin, bottom_right.y - margin),
+            end: Point::new(bottom_right.x - margin, bounding_box.top_left.y + margin),
+            x_min: 0,
+            x_max: (SAMPLES as i32 - 1).max(1),
+            y_min: min.min(max),
+            y_max: max.max(min + 1),
+        };
+        // Display point of the value at `index`, newest first
+        let point = |index: usize| {
+            let value = self.values[index]?;
+            let slot = (SAMPLES - 1 - index) as i32;
+            Some(viewport.scale_point_clamped(Point::new(slot, value)))
+        };
+
+        let style = PrimitiveStyle::with_stroke(self.line_color, 1);
+        for index in 1..SAMPLES {
+            if let (Some(start), Some(end)) = (point(index), point(index - 1)) {
+                Line::new(start, end).into_styled(style).draw(target)?;
+            }
+        }
+
+        let dot = |index: usize, color: C| {
+            point(index).map(|center| {
+                Circle::with_center(center, self.dot_diameter)
+                    .into_styled(PrimitiveStyle::with_fill(color))
+            })
+        };
+        if let (Some(color), Some((min, max))) = (self.min_max_color, self.extremes()) {
+            for index in [min, max] {
+                dot(index, color).map_or(Ok(()), |dot| dot.draw(target))?;
+            }
+        }
+        if let Some(color) = self.last_color {
+            dot(0, color).map_or(Ok(()), |dot| dot.draw(target))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        draw_target::DrawTargetExt, mock_display::MockDisplay, pixelcolor::BinaryColor,
+        prelude::Size, primitives::Rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_auto_range_fills_display() {
+        let mut sparkline = Sparkline::<BinaryColor, 4>::builder()
+            .line_color(BinaryColor::On)
+            .build();
+        for value in [10, 30, 20, 30] {
+            sparkline.push(value);
+        }
+        assert_eq!(Some((10, 30)), sparkline.range());
+        assert_eq!(Some((3, 2)), sparkline.extremes());
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        sparkline
+            .draw(&mut display.cropped(&Rectangle::new(Point::zero(), Size::new(7, 3))))
+            .unwrap();
+        display.assert_pattern(&[
+            "  ##  #", //
+            " #  ## ", //
+            "#      ",
+        ]);
+    }
+}
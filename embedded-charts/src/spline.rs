@@ -0,0 +1,100 @@
+//! Monotone cubic interpolation in fixed point.
+//!
+//! Tangents follow Steffen's method, so the curve between two points is monotone and never
+//! leaves the value range of the points. This keeps smoothed lines inside the axis bounds.
+
+use embedded_graphics::prelude::Point;
+
+/// Fractional bits of the fixed point slopes
+const SLOPE_SHIFT: u32 = 10;
+
+/// Slope from `a` to `b` in fixed point, `None` if they are above each other
+fn secant(a: Point, b: Point) -> Option<i64> {
+    let dx = (b.x - a.x) as i64;
+    (dx != 0).then(|| (((b.y - a.y) as i64) << SLOPE_SHIFT) / dx)
+}
+
+/// Tangent at `p` in fixed point, flat at local extrema and limited so the curve can not
+/// overshoot
+fn tangent(before: Option<Point>, p: Point, after: Option<Point>) -> i64 {
+    match (before, after) {
+        (Some(before), Some(after)) => {
+            let (Some(s0), Some(s1)) = (secant(before, p), secant(p, after)) else {
+                return 0;
+            };
+            if s0.signum() != s1.signum() || s0 == 0 {
+                return 0;
+            }
+            let (h0, h1) = ((p.x - before.x) as i64, (after.x - p.x) as i64);
+            if h0.signum() != h1.signum() {
+                // Points are not ordered by x
+                return 0;
+            }
+            let weighted = (s0 * h1 + s1 * h0) / (h0 + h1);
+            2 * s0.signum() * s0.abs().min(s1.abs()).min(weighted.abs() / 2)
+        }
+        (None, Some(after)) => secant(p, after).unwrap_or(0),
+        (Some(before), None) => secant(before, p).unwrap_or(0),
+        (None, None) => 0,
+    }
+}
+
+/// Points along the monotone curve from `start` to `end`, one every `step` pixels in x.
+/// `before` and `after` are the neighbouring points the curve connects to, if any.
+pub(crate) fn monotone_path(
+    before: Option<Point>,
+    start: Point,
+    end: Point,
+    after: Option<Point>,
+    step: u32,
+) -> impl Iterator<Item = Point> {
+    let h = (end.x - start.x) as i64;
+    let m0 = tangent(before, start, Some(end)) * h;
+    let m1 = tangent(Some(start), end, after) * h;
+    let n = (h.unsigned_abs() / step.max(1) as u64).max(1) as i64;
+    let (y0, y1) = (
+        (start.y as i64) << SLOPE_SHIFT,
+        (end.y as i64) << SLOPE_SHIFT,
+    );
+    let (low, high) = (start.y.min(end.y), start.y.max(end.y));
+
+    (0..=n).map(move |k| {
+        // Cubic Hermite basis functions at t = k / n, multiplied by n³
+        let h00 = 2 * k * k * k - 3 * k * k * n + n * n * n;
+        let h10 = k * k * k - 2 * k * k * n + k * n * n;
+        let h01 = -2 * k * k * k + 3 * k * k * n;
+        let h11 = k * k * k - k * k * n;
+        let divisor = (n * n * n) << SLOPE_SHIFT;
+        let sum = h00 * y0 + h10 * m0 + h01 * y1 + h11 * m1;
+        let y = (sum + divisor / 2).div_euclid(divisor) as i32;
+        Point::new(start.x + (h * k / n) as i32, y.clamp(low, high))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monotone_path_does_not_overshoot() {
+        let before = Point::new(0, 0);
+        let start = Point::new(10, 40);
+        let end = Point::new(20, 41);
+        let after = Point::new(30, 0);
+        let mut previous = start.y;
+        for p in monotone_path(Some(before), start, end, Some(after), 1) {
+            assert!((40..=41).contains(&p.y));
+            assert!(p.y >= previous);
+            previous = p.y;
+        }
+    }
+
+    #[test]
+    fn test_monotone_path_ends_at_points() {
+        let start = Point::new(30, 5);
+        let end = Point::new(0, 25);
+        let mut path = monotone_path(None, start, end, Some(Point::new(-10, 30)), 4);
+        assert_eq!(Some(start), path.next());
+        assert_eq!(Some(end), path.last());
+    }
+}
@@ -0,0 +1,149 @@
+use core::convert::Infallible;
+
+use embedded_graphics::{
+    prelude::{DrawTarget, PixelColor, Point, Primitive},
+    primitives::{Line, PointsIter, PrimitiveStyle},
+    Drawable,
+};
+
+/// Alternating on and off lengths in pixels, starting with on.
+///
+/// For example `[4, 2]` is dashed, `[1, 1]` dotted and `[6, 2, 1, 2]` dash-dot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DashPattern<'a> {
+    lengths: &'a [u32],
+}
+
+impl<'a> DashPattern<'a> {
+    pub const DASHED: DashPattern<'static> = DashPattern::new(&[4, 3]);
+    pub const DOTTED: DashPattern<'static> = DashPattern::new(&[1, 2]);
+    pub const DASH_DOT: DashPattern<'static> = DashPattern::new(&[6, 2, 1, 2]);
+
+    pub const fn new(lengths: &'a [u32]) -> Self {
+        Self { lengths }
+    }
+
+    /// Whether the pixel `position` pixels from the start of the pattern is drawn
+    fn is_on(&self, position: u32) -> bool {
+        let period: u32 = self.lengths.iter().sum();
+        if period == 0 {
+            return true;
+        }
+        let mut position = position % period;
+        for (i, length) in self.lengths.iter().enumerate() {
+            if position < *length {
+                return i.is_multiple_of(2);
+            }
+            position -= length;
+        }
+        true
+    }
+}
+
+/// How a line is stroked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineStyle<'a> {
+    #[default]
+    Solid,
+    Dashed(DashPattern<'a>),
+}
+
+/// Strokes connected lines with a [`LineStyle`], continuing the dash pattern from one line to
+/// the next so polylines, grid lines and threshold lines look even.
+#[derive(Debug)]
+pub struct Stroke<'a, C> {
+    style: LineStyle<'a>,
+    color: C,
+    width: u32,
+    /// Pixels of the pattern used so far
+    position: u32,
+    /// End of the previous line, it is not counted again if the next line starts there
+    last: Option<Point>,
+}
+
+impl<'a, C> Stroke<'a, C>
+where
+    C: PixelColor,
+{
+    pub fn new(style: LineStyle<'a>, color: C, width: u32) -> Self {
+        Self {
+            style,
+            color,
+            width,
+            position: 0,
+            last: None,
+        }
+    }
+
+    /// Starts the pattern from the beginning
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.last = None;
+    }
+
+    pub fn draw_line<D>(&mut self, target: &mut D, start: Point, end: Point) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let style = PrimitiveStyle::with_stroke(self.color, self.width);
+        self.dashes(start, end, |s, e| {
+            Line::new(s, e).into_styled(style).draw(target)
+        })
+    }
+
+    /// Moves the pattern along the line without drawing it, keeping later lines in phase
+    pub fn skip_line(&mut self, start: Point, end: Point) {
+        let _ = self.dashes(start, end, |_, _| Ok::<(), Infallible>(()));
+    }
+
+    /// Calls `draw` with start and end of every dash on the line from `start` to `end`
+    fn dashes<E>(
+        &mut self,
+        start: Point,
+        end: Point,
+        mut draw: impl FnMut(Point, Point) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let skip_first = self.last == Some(start);
+        self.last = Some(end);
+        let pattern = match self.style {
+            LineStyle::Solid => return draw(start, end),
+            LineStyle::Dashed(pattern) => pattern,
+        };
+
+        let mut dash: Option<(Point, Point)> = None;
+        for p in Line::new(start, end).points().skip(skip_first as usize) {
+            let on = pattern.is_on(self.position);
+            self.position = self.position.wrapping_add(1);
+            if on {
+                dash = Some((dash.map_or(p, |(s, _)| s), p));
+            } else if let Some((s, e)) = dash.take() {
+                draw(s, e)?;
+            }
+        }
+        match dash {
+            Some((s, e)) => draw(s, e),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    use super::*;
+
+    #[test]
+    fn test_pattern_continues_over_lines() {
+        let mut display = MockDisplay::new();
+        let pattern = DashPattern::new(&[2, 2]);
+        let mut stroke = Stroke::new(LineStyle::Dashed(pattern), BinaryColor::On, 1);
+        stroke
+            .draw_line(&mut display, Point::new(0, 0), Point::new(2, 0))
+            .unwrap();
+        stroke
+            .draw_line(&mut display, Point::new(2, 0), Point::new(6, 0))
+            .unwrap();
+        display.assert_pattern(&["##  ##"]);
+    }
+}
@@ -0,0 +1,70 @@
+//! Text drawn with [`crate::DEFAULT_FONT`]
+
+use embedded_graphics::prelude::{DrawTarget, Point};
+use u8g2_fonts::{
+    types::{FontColor, HorizontalAlignment, VerticalPosition},
+    Content,
+};
+
+/// Width of `content` in pixels, glyphs missing from the font are not counted
+pub(crate) fn width(content: impl Content) -> i32 {
+    crate::DEFAULT_FONT
+        .get_rendered_dimensions(content, Point::zero(), VerticalPosition::Top)
+        .map_or(0, |d| d.advance.x)
+}
+
+/// Longest start of `text` that fits in `max_width` pixels and whether it was shortened.
+/// A shortened text leaves room for the `.` marking it.
+pub(crate) fn fit(text: &str, max_width: i32) -> (&str, bool) {
+    if width(text) <= max_width {
+        return (text, false);
+    }
+    let max_width = max_width - width('.');
+    let end = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .take_while(|i| width(&text[..*i]) <= max_width)
+        .last()
+        .unwrap_or(0);
+    (&text[..end], true)
+}
+
+/// Draws `content` aligned to `position`. Glyphs missing from the font are skipped, only
+/// errors of the display are returned.
+pub(crate) fn draw<D>(
+    target: &mut D,
+    content: impl Content,
+    position: Point,
+    vertical: VerticalPosition,
+    horizontal: HorizontalAlignment,
+    color: D::Color,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    match crate::DEFAULT_FONT.render_aligned(
+        content,
+        position,
+        vertical,
+        horizontal,
+        FontColor::Transparent(color),
+        target,
+    ) {
+        Err(u8g2_fonts::Error::DisplayError(e)) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_shortens_long_text() {
+        assert_eq!(("temp", false), fit("temp", 16));
+        let (start, shortened) = fit("temperature", 16);
+        assert!(shortened);
+        assert!(width(start) + width('.') <= 16);
+        assert_eq!("tem", start);
+    }
+}
@@ -0,0 +1,60 @@
+use embedded_graphics::{geometry::Angle, prelude::Point};
+
+/// Sine of `degrees` with Bhaskara's approximation, off by less than 0.002
+fn sin(degrees: f32) -> f32 {
+    // Into -180..180
+    let degrees = degrees % 360.0;
+    let degrees = if degrees > 180.0 {
+        degrees - 360.0
+    } else if degrees < -180.0 {
+        degrees + 360.0
+    } else {
+        degrees
+    };
+    let x = if degrees < 0.0 { -degrees } else { degrees };
+    let product = x * (180.0 - x);
+    let sin = 4.0 * product / (40500.0 - product);
+    if degrees < 0.0 {
+        -sin
+    } else {
+        sin
+    }
+}
+
+fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+/// Point `radius` pixels from `center` at `angle`, which is measured clockwise from the
+/// positive x axis like the angles of the embedded-graphics primitives
+pub(crate) fn point_at(center: Point, radius: i32, angle: Angle) -> Point {
+    let degrees = angle.to_degrees();
+    center
+        + Point::new(
+            round(radius as f32 * sin(degrees + 90.0)),
+            round(radius as f32 * sin(degrees)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(0.0, Point::new(100, 0); "right")]
+    #[test_case(90.0, Point::new(0, 100); "down")]
+    #[test_case(-90.0, Point::new(0, -100); "up")]
+    #[test_case(405.0, Point::new(71, 71); "past_full_turn")]
+    #[test_case(210.0, Point::new(-86, -50); "upper_left")]
+    fn test_point_at(degrees: f32, expected: Point) {
+        assert_eq!(
+            expected,
+            point_at(Point::zero(), 100, Angle::from_degrees(degrees))
+        );
+    }
+}
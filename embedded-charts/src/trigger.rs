@@ -0,0 +1,165 @@
+/// Direction the signal has to cross the trigger level to start a capture
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Edge {
+    #[default]
+    Rising,
+    Falling,
+}
+
+/// What happens after a capture is complete
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TriggerMode {
+    /// Re-arm and wait for the next trigger, the last capture stays on screen meanwhile
+    #[default]
+    Normal,
+    /// Capture once and stop until [`Trigger::arm`] is called
+    Single,
+}
+
+/// State of the capture state machine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaptureState {
+    /// Collecting pre-trigger samples and waiting for the trigger edge
+    #[default]
+    Armed,
+    /// Trigger edge seen, collecting the rest of the capture
+    Triggered { remaining: usize },
+    /// Single capture done, new samples are ignored
+    Stopped,
+}
+
+/// Edge trigger that cuts a stream of samples into captures of `SAMPLES` samples.
+///
+/// A capture contains `pre_trigger` samples from before the trigger edge, the sample that
+/// crossed the level and the samples after it.
+#[derive(Debug, bon::Builder)]
+pub struct Trigger<const SAMPLES: usize> {
+    /// Level the signal has to cross, in chart coordinates
+    level: i32,
+    #[builder(default)]
+    edge: Edge,
+    #[builder(default)]
+    mode: TriggerMode,
+    /// Number of samples kept from before the trigger edge
+    #[builder(default = SAMPLES / 4)]
+    pre_trigger: usize,
+    #[builder(skip)]
+    state: CaptureState,
+    /// Ring buffer of the samples of the ongoing capture
+    #[builder(skip = [0; SAMPLES])]
+    buffer: [i32; SAMPLES],
+    /// Index the next sample is written to in `buffer`
+    #[builder(skip)]
+    write: usize,
+    /// Number of valid samples in `buffer`
+    #[builder(skip)]
+    filled: usize,
+    #[builder(skip)]
+    previous: Option<i32>,
+}
+
+impl<const SAMPLES: usize> Trigger<SAMPLES> {
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    pub fn set_level(&mut self, level: i32) {
+        self.level = level;
+    }
+
+    pub fn state(&self) -> CaptureState {
+        self.state
+    }
+
+    /// Starts waiting for a new trigger, discarding the samples of an ongoing capture
+    pub fn arm(&mut self) {
+        self.state = CaptureState::Armed;
+        self.filled = 0;
+        self.previous = None;
+    }
+
+    /// Feeds a sample to the state machine. Returns the capture, oldest sample first, when it
+    /// is complete. Samples missing from the start of the capture are `None`.
+    pub fn feed(&mut self, value: i32) -> Option<[Option<i32>; SAMPLES]> {
+        let previous = self.previous.replace(value);
+        match self.state {
+            CaptureState::Stopped => return None,
+            CaptureState::Armed => {
+                self.store(value);
+                let crossed = previous.is_some_and(|previous| match self.edge {
+                    Edge::Rising => previous < self.level && value >= self.level,
+                    Edge::Falling => previous > self.level && value <= self.level,
+                });
+                if crossed {
+                    // Keep only the wanted amount of pre-trigger samples
+                    self.filled = self.filled.min(self.pre_trigger.min(SAMPLES - 1) + 1);
+                    self.state = CaptureState::Triggered {
+                        remaining: SAMPLES - self.filled,
+                    };
+                }
+            }
+            CaptureState::Triggered { remaining } => {
+                self.store(value);
+                self.state = CaptureState::Triggered {
+                    remaining: remaining - 1,
+                };
+            }
+        }
+
+        if self.state != (CaptureState::Triggered { remaining: 0 }) {
+            return None;
+        }
+        let capture = self.capture();
+        match self.mode {
+            TriggerMode::Normal => self.arm(),
+            TriggerMode::Single => self.state = CaptureState::Stopped,
+        }
+        Some(capture)
+    }
+
+    fn store(&mut self, value: i32) {
+        self.buffer[self.write] = value;
+        self.write = (self.write + 1) % SAMPLES;
+        self.filled = (self.filled + 1).min(SAMPLES);
+    }
+
+    /// Valid samples of `buffer` in the order they were stored, aligned to the end
+    fn capture(&self) -> [Option<i32>; SAMPLES] {
+        let missing = SAMPLES - self.filled;
+        core::array::from_fn(|i| (i >= missing).then(|| self.buffer[(self.write + i) % SAMPLES]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rising_edge_with_pre_trigger() {
+        let mut trigger = Trigger::<6>::builder().level(5).pre_trigger(2).build();
+        let samples = [0, 1, 2, 3, 6, 7, 8, 9, 10];
+        let capture = samples.iter().find_map(|s| trigger.feed(*s));
+        assert_eq!(
+            Some([Some(2), Some(3), Some(6), Some(7), Some(8), Some(9)]),
+            capture
+        );
+        assert_eq!(CaptureState::Armed, trigger.state());
+    }
+
+    #[test]
+    fn test_falling_edge_single_shot() {
+        let mut trigger = Trigger::<3>::builder()
+            .level(0)
+            .edge(Edge::Falling)
+            .mode(TriggerMode::Single)
+            .pre_trigger(0)
+            .build();
+        assert_eq!(None, trigger.feed(5));
+        assert_eq!(None, trigger.feed(-1));
+        assert_eq!(None, trigger.feed(-2));
+        assert_eq!(Some([Some(-1), Some(-2), Some(-3)]), trigger.feed(-3));
+        assert_eq!(CaptureState::Stopped, trigger.state());
+        assert_eq!(None, trigger.feed(5));
+        assert_eq!(None, trigger.feed(-5));
+    }
+}
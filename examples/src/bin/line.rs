@@ -66,9 +66,6 @@ fn main() -> ! {
 
     display.clear(Rgb565::BLACK).unwrap();
     println!("{:?}", display.size());
-    line_plot.scale_points_to_display(&display.size());
-
-    println!("{:?}", line_plot.get_points());
     line_plot.draw(&mut display).unwrap();
     println!("Done");
 